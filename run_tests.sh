@@ -33,13 +33,17 @@ fi
 mv ./target/debug/cp-organization ./cp-organization
 mv ./target/debug/test_create_organization_successfully ./test_create_organization_successfully
 mv ./target/debug/test_create_invitation_code_successfully ./test_create_invitation_code_successfully
+mv ./target/debug/test_get_organization_successfully ./test_get_organization_successfully
+mv ./target/debug/test_redeem_invitation_code_successfully ./test_redeem_invitation_code_successfully
 
 result_exit_code=0
 
 DEFAULT_AMQP_API_FILE="./config/dev/amqp_api.json"
 export CP_ORGANIZATION_AMQP_API_FILE=${CP_ORGANIZATION_AMQP_API_FILE:=$DEFAULT_AMQP_API_FILE}
+DEFAULT_OPENID_CONNECT_CONFIG_FILE="./test_fixtures/openid_connect/openid_connect_config.json"
+export CP_ORGANIZATION_OPENID_CONNECT_CONFIG_FILE=${CP_ORGANIZATION_OPENID_CONNECT_CONFIG_FILE:=$DEFAULT_OPENID_CONNECT_CONFIG_FILE}
 
-./cp-organization $CP_ORGANIZATION_AMQP_API_FILE &
+./cp-organization $CP_ORGANIZATION_AMQP_API_FILE $CP_ORGANIZATION_OPENID_CONNECT_CONFIG_FILE &
 impl_pid=$!
 
 sleep 1
@@ -102,6 +106,44 @@ sleep 1
 create_invitation_code_successfully
 create_invitation_code_successfully
 
+# TEST GET ORGANIZATION SUCCESSFULLY, EXPECTED EXIT CODE: 0
+
+get_organization_successfully() {
+  db_init
+
+  ./test_get_organization_successfully $CP_ORGANIZATION_TEST_AMQP_CONNECTION_URI
+
+  test_get_organization_successfully_code=$?
+
+  if [ $test_get_organization_successfully_code -eq 0 ]; then
+    echo "Test get organization successfully: SUCCESS"
+  else
+    echo "Test get organization successfully: FAILED"
+    result_exit_code=1
+  fi
+}
+
+get_organization_successfully
+
+# TEST REDEEM INVITATION CODE SUCCESSFULLY, EXPECTED EXIT CODE: 0
+
+redeem_invitation_code_successfully() {
+  db_init
+
+  ./test_redeem_invitation_code_successfully $CP_ORGANIZATION_TEST_AMQP_CONNECTION_URI
+
+  test_redeem_invitation_code_successfully_code=$?
+
+  if [ $test_redeem_invitation_code_successfully_code -eq 0 ]; then
+    echo "Test redeem invitation code successfully: SUCCESS"
+  else
+    echo "Test redeem invitation code successfully: FAILED"
+    result_exit_code=1
+  fi
+}
+
+redeem_invitation_code_successfully
+
 sleep 5
 
 kill $impl_pid
@@ -109,5 +151,7 @@ kill $impl_pid
 rm ./cp-organization
 rm ./test_create_organization_successfully
 rm ./test_create_invitation_code_successfully
+rm ./test_get_organization_successfully
+rm ./test_redeem_invitation_code_successfully
 
 exit $result_exit_code
\ No newline at end of file
@@ -0,0 +1,47 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{actions::member_action::MemberAction, logic_request::LogicRequest};
+
+const TIMEOUT_CHECK_PERMISSION_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct CheckPermission {
+    user_id: String,
+    org_id: String,
+    permission: String,
+}
+
+pub async fn check_permission(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: CheckPermission = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<bool, Error>>();
+
+    let logic_action = MemberAction::CheckPermission {
+        user_id: payload.user_id,
+        org_id: payload.org_id,
+        permission: payload.permission,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Member(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_CHECK_PERMISSION_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
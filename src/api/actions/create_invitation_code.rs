@@ -17,7 +17,12 @@ const TIMEOUT_CREATE_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
 pub struct CreateInvitationCode {
     org_id: String,
     permissions: Vec<String>,
+    #[serde(default)]
+    denied_permissions: Vec<String>,
     roles: Vec<String>,
+    expires_at: Option<String>,
+    max_uses: Option<u32>,
+    user_id: String,
 }
 
 pub async fn create_invitation_code(
@@ -31,7 +36,11 @@ pub async fn create_invitation_code(
         crate::logic::actions::invitation_code_action::InvitationCodeAction::Create {
             org_id: payload.org_id,
             permissions: payload.permissions,
+            denied_permissions: payload.denied_permissions,
             roles: payload.roles,
+            expires_at: payload.expires_at,
+            max_uses: payload.max_uses,
+            user_id: payload.user_id,
             replier,
         };
 
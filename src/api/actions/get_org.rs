@@ -0,0 +1,121 @@
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+
+use crate::{
+    logic::{actions::organization_action::OrganizationAction, logic_request::LogicRequest},
+    storage::organization::Organization,
+};
+
+use async_channel::Sender;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const TIMEOUT_GET_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct GetOrganization {
+    id: String,
+    user_id: String,
+}
+
+pub async fn get_org(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: GetOrganization = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<Organization, Error>>();
+
+    let logic_action = OrganizationAction::Get {
+        id: payload.id,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Organization(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_GET_ORGANIZATION_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
+
+#[cfg(test)]
+use std::time::Duration;
+
+#[cfg(test)]
+use cp_microservice::{api::shared::request_header::RequestHeader, core::error::ErrorKind};
+#[cfg(test)]
+use tokio::time::timeout;
+
+#[cfg(test)]
+const TIMEOUT_AFTER_MILLISECONDS: u64 = 200u64;
+
+#[tokio::test]
+pub async fn error_when_serializing_fails() {
+    let request_header: RequestHeader =
+        RequestHeader::new("example:action".to_string(), "token".to_string());
+    let request: Request = Request::new(request_header, Value::Null);
+
+    let (sender, _receiver) = async_channel::bounded(1024usize);
+
+    match get_org(request, sender).await {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::RequestError, error.kind),
+    }
+}
+
+#[tokio::test]
+pub async fn sends_expected_logic_request() {
+    const EXAMPLE_ORGANIZATION_ID: &str = "653846b428c2649821284c60";
+
+    let request_header: RequestHeader =
+        RequestHeader::new("example:action".to_string(), "token".to_string());
+    let get_organization_payload = match serde_json::to_value(GetOrganization {
+        id: EXAMPLE_ORGANIZATION_ID.to_string(),
+        user_id: "gabriel".to_string(),
+    }) {
+        Ok(payload) => payload,
+        Err(error) => panic!("failed to serialize GetOrganization payload: {}", error),
+    };
+
+    let request: Request = Request::new(request_header, get_organization_payload);
+
+    let (sender, receiver) = async_channel::bounded(1024usize);
+
+    tokio::spawn(async move {
+        let _ = get_org(request, sender).await;
+    });
+
+    let logic_request = match timeout(
+        Duration::from_millis(TIMEOUT_AFTER_MILLISECONDS),
+        receiver.recv(),
+    )
+    .await
+    .unwrap()
+    {
+        Ok(request) => request,
+        Err(error) => panic!("failed to receive 'LogicRequest': {}", error),
+    };
+
+    match logic_request {
+        LogicRequest::Organization(action) => match action {
+            Some(action) => match action {
+                OrganizationAction::Get { id, .. } => {
+                    assert_eq!(EXAMPLE_ORGANIZATION_ID.to_string(), id)
+                }
+                _ => panic!("unexpected 'action' type found"),
+            },
+            None => panic!("expected 'Some' got 'None'"),
+        },
+        _ => panic!("unexpected 'logic_request' type found"),
+    }
+}
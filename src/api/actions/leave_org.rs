@@ -0,0 +1,45 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{actions::member_action::MemberAction, logic_request::LogicRequest};
+
+const TIMEOUT_LEAVE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct LeaveOrganization {
+    org_id: String,
+    user_id: String,
+}
+
+pub async fn leave_org(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: LeaveOrganization = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = MemberAction::Leave {
+        org_id: payload.org_id,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Member(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_LEAVE_ORGANIZATION_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
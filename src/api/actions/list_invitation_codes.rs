@@ -0,0 +1,47 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    logic::{actions::invitation_code_action::InvitationCodeAction, logic_request::LogicRequest},
+    storage::invitation_code::InvitationCode,
+};
+
+const TIMEOUT_LIST_INVITATION_CODES_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct ListInvitationCodes {
+    org_id: String,
+    user_id: String,
+}
+
+pub async fn list_invitation_codes(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: ListInvitationCodes = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<Vec<InvitationCode>, Error>>();
+    let logic_action = InvitationCodeAction::List {
+        org_id: payload.org_id,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::InvitationCode(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_LIST_INVITATION_CODES_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
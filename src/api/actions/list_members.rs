@@ -0,0 +1,121 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    logic::{actions::member_action::MemberAction, logic_request::LogicRequest},
+    storage::member_page::MemberPage,
+};
+
+const TIMEOUT_LIST_MEMBERS_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct ListMembers {
+    org_id: String,
+    user_id: String,
+    role_id: Option<String>,
+    sort_by: Option<String>,
+    descending: Option<bool>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+}
+
+pub async fn list_members(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: ListMembers = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<MemberPage, Error>>();
+
+    let logic_action = MemberAction::List {
+        org_id: payload.org_id,
+        user_id: payload.user_id,
+        role_id: payload.role_id,
+        sort_by: payload.sort_by,
+        descending: payload.descending,
+        cursor: payload.cursor,
+        limit: payload.limit,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Member(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_LIST_MEMBERS_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
+
+#[cfg(test)]
+use std::time::Duration;
+
+#[cfg(test)]
+use cp_microservice::api::shared::request_header::RequestHeader;
+#[cfg(test)]
+use tokio::time::timeout;
+
+#[cfg(test)]
+const TIMEOUT_AFTER_MILLISECONDS: u64 = 200u64;
+
+#[tokio::test]
+pub async fn sends_optional_fields_as_none_when_missing() {
+    const EXAMPLE_ORGANIZATION_ID: &str = "653846b428c2649821284c60";
+
+    let request_header: RequestHeader =
+        RequestHeader::new("example:action".to_string(), "token".to_string());
+    let request: Request = Request::new(
+        request_header,
+        serde_json::json!({
+            "org_id": EXAMPLE_ORGANIZATION_ID,
+            "user_id": "gabriel"
+        }),
+    );
+
+    let (sender, receiver) = async_channel::bounded(1024usize);
+
+    tokio::spawn(async move {
+        let _ = list_members(request, sender).await;
+    });
+
+    let logic_request = match timeout(
+        Duration::from_millis(TIMEOUT_AFTER_MILLISECONDS),
+        receiver.recv(),
+    )
+    .await
+    .unwrap()
+    {
+        Ok(request) => request,
+        Err(error) => panic!("failed to receive 'LogicRequest': {}", error),
+    };
+
+    match logic_request {
+        LogicRequest::Member(Some(MemberAction::List {
+            org_id,
+            role_id,
+            sort_by,
+            descending,
+            cursor,
+            limit,
+            ..
+        })) => {
+            assert_eq!(EXAMPLE_ORGANIZATION_ID.to_string(), org_id);
+            assert_eq!(None, role_id);
+            assert_eq!(None, sort_by);
+            assert_eq!(None, descending);
+            assert_eq!(None, cursor);
+            assert_eq!(None, limit);
+        }
+        _ => panic!("unexpected 'logic_request' type found"),
+    }
+}
@@ -0,0 +1,48 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    logic::{actions::role_action::RoleAction, logic_request::LogicRequest},
+    storage::role::Role,
+};
+
+const TIMEOUT_LIST_ROLES_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct ListRoles {
+    org_id: String,
+    user_id: String,
+}
+
+pub async fn list_roles(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: ListRoles = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<Vec<Role>, Error>>();
+
+    let logic_action = RoleAction::List {
+        org_id: payload.org_id,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Role(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_LIST_ROLES_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
@@ -0,0 +1,47 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    logic::{actions::member_action::MemberAction, logic_request::LogicRequest},
+    storage::user_organization::UserOrganization,
+};
+
+const TIMEOUT_LIST_USER_ORGANIZATIONS_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct ListUserOrganizations {
+    user_id: String,
+}
+
+pub async fn list_user_orgs(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: ListUserOrganizations = extract_payload(&request)?;
+
+    let (replier, receiver) =
+        tokio::sync::oneshot::channel::<Result<Vec<UserOrganization>, Error>>();
+
+    let logic_action = MemberAction::ListUserOrganizations {
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Member(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_LIST_USER_ORGANIZATIONS_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
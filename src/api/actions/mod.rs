@@ -1,2 +1,21 @@
+pub mod check_permission;
 pub mod create_invitation_code;
 pub mod create_org;
+pub mod create_role;
+pub mod delete_org;
+pub mod delete_role;
+pub mod get_org;
+pub mod leave_org;
+pub mod list_invitation_codes;
+pub mod list_members;
+pub mod list_roles;
+pub mod list_user_orgs;
+pub mod redeem_invitation_code;
+pub mod remove_member;
+pub mod restore_org;
+pub mod revoke_invitation_code;
+pub mod transfer_ownership;
+pub mod update_member_permissions;
+pub mod update_member_roles;
+pub mod update_org;
+pub mod update_role;
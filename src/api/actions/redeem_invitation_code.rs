@@ -0,0 +1,46 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{
+    actions::invitation_code_action::InvitationCodeAction, logic_request::LogicRequest,
+};
+
+const TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct RedeemInvitationCode {
+    code: String,
+    user_id: String,
+}
+
+pub async fn redeem_invitation_code(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: RedeemInvitationCode = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<String, Error>>();
+    let logic_action = InvitationCodeAction::Redeem {
+        code: payload.code,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::InvitationCode(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
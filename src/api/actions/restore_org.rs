@@ -0,0 +1,46 @@
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+
+use crate::logic::{actions::organization_action::OrganizationAction, logic_request::LogicRequest};
+
+use async_channel::Sender;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const TIMEOUT_RESTORE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct RestoreOrganization {
+    id: String,
+    user_id: String,
+}
+
+pub async fn restore_org(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: RestoreOrganization = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = OrganizationAction::Restore {
+        id: payload.id,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Organization(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_RESTORE_ORGANIZATION_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
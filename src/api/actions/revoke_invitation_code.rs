@@ -0,0 +1,48 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{
+    actions::invitation_code_action::InvitationCodeAction, logic_request::LogicRequest,
+};
+
+const TIMEOUT_REVOKE_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct RevokeInvitationCode {
+    org_id: String,
+    code: String,
+    user_id: String,
+}
+
+pub async fn revoke_invitation_code(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: RevokeInvitationCode = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+    let logic_action = InvitationCodeAction::Revoke {
+        org_id: payload.org_id,
+        code: payload.code,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::InvitationCode(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_REVOKE_INVITATION_CODE_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
@@ -0,0 +1,50 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{actions::organization_action::OrganizationAction, logic_request::LogicRequest};
+
+const TIMEOUT_TRANSFER_OWNERSHIP_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct TransferOwnership {
+    id: String,
+    user_id: String,
+    new_owner_id: String,
+    #[serde(default)]
+    demote_previous_owner: bool,
+}
+
+pub async fn transfer_ownership(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: TransferOwnership = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = OrganizationAction::TransferOwnership {
+        id: payload.id,
+        user_id: payload.user_id,
+        new_owner_id: payload.new_owner_id,
+        demote_previous_owner: payload.demote_previous_owner,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Organization(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_TRANSFER_OWNERSHIP_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
@@ -0,0 +1,51 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{actions::member_action::MemberAction, logic_request::LogicRequest};
+
+const TIMEOUT_UPDATE_MEMBER_PERMISSIONS_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateMemberPermissions {
+    org_id: String,
+    user_id: String,
+    member_user_id: String,
+    permissions: Option<Vec<String>>,
+    denied_permissions: Option<Vec<String>>,
+}
+
+pub async fn update_member_permissions(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: UpdateMemberPermissions = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = MemberAction::UpdatePermissions {
+        org_id: payload.org_id,
+        user_id: payload.user_id,
+        member_user_id: payload.member_user_id,
+        permissions: payload.permissions,
+        denied_permissions: payload.denied_permissions,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Member(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_UPDATE_MEMBER_PERMISSIONS_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
@@ -0,0 +1,127 @@
+use cp_core::geolocalization::address::Address;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+
+use crate::logic::{actions::organization_action::OrganizationAction, logic_request::LogicRequest};
+
+use async_channel::Sender;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const TIMEOUT_UPDATE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateOrganization {
+    id: String,
+    country: Option<String>,
+    name: Option<String>,
+    address: Option<Address>,
+    user_id: String,
+}
+
+pub async fn update_org(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: UpdateOrganization = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = OrganizationAction::Update {
+        id: payload.id,
+        country: payload.country,
+        name: payload.name,
+        address: payload.address,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Organization(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_UPDATE_ORGANIZATION_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
+
+#[cfg(test)]
+use std::time::Duration;
+
+#[cfg(test)]
+use cp_microservice::{api::shared::request_header::RequestHeader, core::error::ErrorKind};
+#[cfg(test)]
+use tokio::time::timeout;
+
+#[cfg(test)]
+const TIMEOUT_AFTER_MILLISECONDS: u64 = 200u64;
+
+#[tokio::test]
+pub async fn error_when_id_is_missing() {
+    let request_header: RequestHeader =
+        RequestHeader::new("example:action".to_string(), "token".to_string());
+    let request: Request = Request::new(request_header, serde_json::json!({ "name": "example" }));
+
+    let (sender, _receiver) = async_channel::bounded(1024usize);
+
+    match update_org(request, sender).await {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::RequestError, error.kind),
+    }
+}
+
+#[tokio::test]
+pub async fn sends_only_supplied_fields() {
+    const EXAMPLE_ORGANIZATION_ID: &str = "653846b428c2649821284c60";
+
+    let request_header: RequestHeader =
+        RequestHeader::new("example:action".to_string(), "token".to_string());
+    let request: Request = Request::new(
+        request_header,
+        serde_json::json!({
+            "id": EXAMPLE_ORGANIZATION_ID,
+            "name": "renamed",
+            "user_id": "gabriel"
+        }),
+    );
+
+    let (sender, receiver) = async_channel::bounded(1024usize);
+
+    tokio::spawn(async move {
+        let _ = update_org(request, sender).await;
+    });
+
+    let logic_request = match timeout(
+        Duration::from_millis(TIMEOUT_AFTER_MILLISECONDS),
+        receiver.recv(),
+    )
+    .await
+    .unwrap()
+    {
+        Ok(request) => request,
+        Err(error) => panic!("failed to receive 'LogicRequest': {}", error),
+    };
+
+    match logic_request {
+        LogicRequest::Organization(Some(OrganizationAction::Update {
+            id,
+            country,
+            name,
+            address,
+            ..
+        })) => {
+            assert_eq!(EXAMPLE_ORGANIZATION_ID.to_string(), id);
+            assert_eq!(None, country);
+            assert_eq!(Some("renamed".to_string()), name);
+            assert!(address.is_none());
+        }
+        _ => panic!("unexpected 'logic_request' type found"),
+    }
+}
@@ -0,0 +1,53 @@
+use async_channel::Sender;
+use cp_microservice::{
+    api::{
+        server::input::{action::extract_payload, api_action::api_action},
+        shared::request::Request,
+    },
+    core::error::Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::logic::{actions::role_action::RoleAction, logic_request::LogicRequest};
+
+const TIMEOUT_UPDATE_ROLE_IN_MILLISECONDS: u64 = 10000u64;
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateRole {
+    id: String,
+    org_id: String,
+    name: Option<String>,
+    permissions: Option<Vec<String>>,
+    parents: Option<Vec<String>>,
+    user_id: String,
+}
+
+pub async fn update_role(
+    request: Request,
+    logic_request_sender: Sender<LogicRequest>,
+) -> Result<Value, Error> {
+    let payload: UpdateRole = extract_payload(&request)?;
+
+    let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let logic_action = RoleAction::Update {
+        id: payload.id,
+        org_id: payload.org_id,
+        name: payload.name,
+        permissions: payload.permissions,
+        parents: payload.parents,
+        user_id: payload.user_id,
+        replier,
+    };
+
+    let logic_request = LogicRequest::Role(Some(logic_action));
+
+    api_action(
+        logic_request,
+        logic_request_sender,
+        TIMEOUT_UPDATE_ROLE_IN_MILLISECONDS,
+        receiver,
+    )
+    .await
+}
@@ -2,7 +2,12 @@ use std::{collections::HashMap, sync::Arc};
 
 use cp_microservice::api::server::input::action::Action;
 
-use crate::logic::logic_request::LogicRequest;
+use crate::{
+    api::plugins::{
+        openid_connect::OPENID_CONNECT_PLUGIN_ID, service_token::SERVICE_TOKEN_PLUGIN_ID,
+    },
+    logic::logic_request::LogicRequest,
+};
 
 pub fn get_api_actions() -> HashMap<String, Action<LogicRequest>> {
     let mut actions: HashMap<String, Action<LogicRequest>> = HashMap::new();
@@ -14,7 +19,66 @@ pub fn get_api_actions() -> HashMap<String, Action<LogicRequest>> {
             Arc::new(move |request, sender| {
                 Box::pin(crate::api::actions::create_org::create_org(request, sender))
             }),
-            Vec::new(),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "get_org".to_string(),
+        Action::new(
+            "get_org".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::get_org::get_org(request, sender))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "update_org".to_string(),
+        Action::new(
+            "update_org".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::update_org::update_org(request, sender))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "delete_org".to_string(),
+        Action::new(
+            "delete_org".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::delete_org::delete_org(request, sender))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "restore_org".to_string(),
+        Action::new(
+            "restore_org".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::restore_org::restore_org(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "transfer_ownership".to_string(),
+        Action::new(
+            "transfer_ownership".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::transfer_ownership::transfer_ownership(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
         ),
     );
 
@@ -29,7 +93,195 @@ pub fn get_api_actions() -> HashMap<String, Action<LogicRequest>> {
                     ),
                 )
             }),
-            Vec::new(),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "redeem_invitation_code".to_string(),
+        Action::new(
+            "redeem_invitation_code".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(
+                    crate::api::actions::redeem_invitation_code::redeem_invitation_code(
+                        request, sender,
+                    ),
+                )
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "list_invitation_codes".to_string(),
+        Action::new(
+            "list_invitation_codes".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(
+                    crate::api::actions::list_invitation_codes::list_invitation_codes(
+                        request, sender,
+                    ),
+                )
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "revoke_invitation_code".to_string(),
+        Action::new(
+            "revoke_invitation_code".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(
+                    crate::api::actions::revoke_invitation_code::revoke_invitation_code(
+                        request, sender,
+                    ),
+                )
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "list_user_orgs".to_string(),
+        Action::new(
+            "list_user_orgs".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::list_user_orgs::list_user_orgs(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "list_members".to_string(),
+        Action::new(
+            "list_members".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::list_members::list_members(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "update_member_roles".to_string(),
+        Action::new(
+            "update_member_roles".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(
+                    crate::api::actions::update_member_roles::update_member_roles(request, sender),
+                )
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "update_member_permissions".to_string(),
+        Action::new(
+            "update_member_permissions".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(
+                    crate::api::actions::update_member_permissions::update_member_permissions(
+                        request, sender,
+                    ),
+                )
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "remove_member".to_string(),
+        Action::new(
+            "remove_member".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::remove_member::remove_member(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "leave_org".to_string(),
+        Action::new(
+            "leave_org".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::leave_org::leave_org(request, sender))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "create_role".to_string(),
+        Action::new(
+            "create_role".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::create_role::create_role(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "update_role".to_string(),
+        Action::new(
+            "update_role".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::update_role::update_role(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "delete_role".to_string(),
+        Action::new(
+            "delete_role".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::delete_role::delete_role(
+                    request, sender,
+                ))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    actions.insert(
+        "list_roles".to_string(),
+        Action::new(
+            "list_roles".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::list_roles::list_roles(request, sender))
+            }),
+            vec![OPENID_CONNECT_PLUGIN_ID.to_string()],
+        ),
+    );
+
+    // Queried by other services on behalf of arbitrary users, so the calling service is
+    // authenticated through its service token and the payload's user id is kept as is.
+    actions.insert(
+        "check_permission".to_string(),
+        Action::new(
+            "check_permission".to_string(),
+            Arc::new(move |request, sender| {
+                Box::pin(crate::api::actions::check_permission::check_permission(
+                    request, sender,
+                ))
+            }),
+            vec![SERVICE_TOKEN_PLUGIN_ID.to_string()],
         ),
     );
 
@@ -1,9 +1,38 @@
 use std::sync::Arc;
 
 use cp_microservice::{api::server::input::input_plugin::InputPlugin, core::error::Error};
+use jsonwebtoken::jwk::JwkSet;
 
-pub async fn get_api_plugins() -> Result<Vec<Arc<dyn InputPlugin + Send + Sync>>, Error> {
-    let api_plugins: Vec<Arc<dyn InputPlugin + Send + Sync>> = vec![];
+use crate::api::{
+    json_web_keys::JsonWebKeys,
+    plugins::{
+        openid_connect::{OpenIdConnectConfig, OpenIdConnectPlugin},
+        service_token::ServiceTokenPlugin,
+    },
+};
+
+pub async fn get_api_plugins(
+    openid_connect_config: &OpenIdConnectConfig,
+    json_web_key_set: JwkSet,
+) -> Result<Vec<Arc<dyn InputPlugin + Send + Sync>>, Error> {
+    // Key sets read from a file are fixed, only downloaded ones are refetched on rotation.
+    let jwks_uri = match openid_connect_config.jwks_file() {
+        Some(_) => None,
+        None => openid_connect_config.jwks_uri().map(str::to_string),
+    };
+
+    let json_web_keys = Arc::new(JsonWebKeys::new(json_web_key_set, jwks_uri));
+
+    let api_plugins: Vec<Arc<dyn InputPlugin + Send + Sync>> = vec![
+        Arc::new(OpenIdConnectPlugin::new(
+            openid_connect_config,
+            json_web_keys.clone(),
+        )),
+        Arc::new(ServiceTokenPlugin::new(
+            openid_connect_config,
+            json_web_keys,
+        )),
+    ];
 
     Ok(api_plugins)
 }
@@ -0,0 +1,110 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{decode_header, jwk::JwkSet};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::init::fetch_json_web_key_set;
+
+/// Tokens naming an unknown key trigger at most one refetch per interval, so forged key ids
+/// cannot flood the identity provider.
+const JSON_WEB_KEY_SET_REFETCH_INTERVAL_IN_MILLISECONDS: u64 = 60000u64;
+
+/// Key set used to verify access tokens. The identity provider rotates its signing keys, so the
+/// set is refetched from `jwks_uri` when a token names a key it does not hold yet. Key sets
+/// read from a file are never refetched.
+pub struct JsonWebKeys {
+    json_web_key_set: RwLock<Arc<JwkSet>>,
+    jwks_uri: Option<String>,
+    refetched_at: Mutex<Option<Instant>>,
+}
+
+impl JsonWebKeys {
+    pub fn new(json_web_key_set: JwkSet, jwks_uri: Option<String>) -> Self {
+        Self {
+            json_web_key_set: RwLock::new(Arc::new(json_web_key_set)),
+            jwks_uri,
+            refetched_at: Mutex::new(None),
+        }
+    }
+
+    /// Returns the key set to verify `token` with, refetching it first when the key named by
+    /// the token is unknown.
+    pub async fn for_token(&self, token: &str) -> Arc<JwkSet> {
+        let json_web_key_set = self.json_web_key_set.read().await.clone();
+
+        let key_id = match decode_header(token) {
+            Ok(header) => header.kid,
+            Err(_) => None,
+        };
+
+        match key_id {
+            Some(key_id) if json_web_key_set.find(&key_id).is_none() => {
+                self.refetch(&key_id).await.unwrap_or(json_web_key_set)
+            }
+            _ => json_web_key_set,
+        }
+    }
+
+    async fn refetch(&self, key_id: &str) -> Option<Arc<JwkSet>> {
+        let jwks_uri = self.jwks_uri.as_ref()?;
+
+        let mut refetched_at = self.refetched_at.lock().await;
+
+        // Another request may have refetched the key set while this one waited for the lock.
+        let json_web_key_set = self.json_web_key_set.read().await.clone();
+
+        if json_web_key_set.find(key_id).is_some() || !is_refetch_due(*refetched_at, Instant::now())
+        {
+            return Some(json_web_key_set);
+        }
+
+        *refetched_at = Some(Instant::now());
+
+        match fetch_json_web_key_set(jwks_uri).await {
+            Ok(json_web_key_set) => {
+                let json_web_key_set = Arc::new(json_web_key_set);
+
+                *self.json_web_key_set.write().await = json_web_key_set.clone();
+
+                Some(json_web_key_set)
+            }
+            Err(error) => {
+                log::warn!("failed to refetch jwks for key id '{}': {}", key_id, &error);
+
+                None
+            }
+        }
+    }
+}
+
+fn is_refetch_due(refetched_at: Option<Instant>, now: Instant) -> bool {
+    match refetched_at {
+        Some(refetched_at) => {
+            now.duration_since(refetched_at)
+                >= Duration::from_millis(JSON_WEB_KEY_SET_REFETCH_INTERVAL_IN_MILLISECONDS)
+        }
+        None => true,
+    }
+}
+
+#[test]
+pub fn refetch_is_due_when_never_refetched() {
+    assert!(is_refetch_due(None, Instant::now()));
+}
+
+#[test]
+pub fn refetch_is_not_due_within_interval() {
+    let now = Instant::now();
+
+    assert!(!is_refetch_due(
+        Some(now),
+        now + Duration::from_millis(1000u64)
+    ));
+    assert!(is_refetch_due(
+        Some(now),
+        now + Duration::from_millis(JSON_WEB_KEY_SET_REFETCH_INTERVAL_IN_MILLISECONDS)
+    ));
+}
@@ -1,3 +1,5 @@
 pub mod actions;
 pub mod api_actions;
 pub mod api_plugins;
+pub mod json_web_keys;
+pub mod plugins;
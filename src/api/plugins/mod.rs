@@ -0,0 +1,2 @@
+pub mod openid_connect;
+pub mod service_token;
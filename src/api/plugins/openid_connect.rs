@@ -0,0 +1,274 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use cp_microservice::{
+    api::{
+        server::input::input_plugin::InputPlugin,
+        shared::{request::Request, request_header::RequestHeader},
+    },
+    core::error::{Error, ErrorKind},
+};
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+
+use crate::api::json_web_keys::JsonWebKeys;
+
+pub const OPENID_CONNECT_PLUGIN_ID: &str = "openid_connect";
+
+const USER_ID_PAYLOAD_KEY: &str = "user_id";
+
+#[derive(Deserialize)]
+pub struct OpenIdConnectConfig {
+    issuer: String,
+    audience: String,
+    jwks_uri: Option<String>,
+    jwks_file: Option<String>,
+}
+
+impl OpenIdConnectConfig {
+    pub fn jwks_uri(&self) -> Option<&str> {
+        self.jwks_uri.as_deref()
+    }
+
+    pub fn jwks_file(&self) -> Option<&str> {
+        self.jwks_file.as_deref()
+    }
+
+    /// Accepts RS256 access tokens issued by the configured issuer for the configured audience.
+    pub fn validation(&self) -> Validation {
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+
+        validation
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// Authenticates the caller through the OpenID Connect access token carried by the request
+/// header and overrides the payload's `user_id` with the token's subject.
+pub struct OpenIdConnectPlugin {
+    json_web_keys: Arc<JsonWebKeys>,
+    validation: Validation,
+}
+
+impl OpenIdConnectPlugin {
+    pub fn new(config: &OpenIdConnectConfig, json_web_keys: Arc<JsonWebKeys>) -> Self {
+        Self {
+            json_web_keys,
+            validation: config.validation(),
+        }
+    }
+
+    async fn authenticate(&self, token: &str) -> Result<String, Error> {
+        let json_web_key_set = self.json_web_keys.for_token(token).await;
+
+        let claims: Claims = decode_token(&json_web_key_set, &self.validation, token)?;
+
+        Ok(claims.sub)
+    }
+}
+
+/// Verifies the token's signature against the key it names and validates its claims.
+pub fn decode_token<C: DeserializeOwned>(
+    json_web_key_set: &JwkSet,
+    validation: &Validation,
+    token: &str,
+) -> Result<C, Error> {
+    let header = match decode_header(token) {
+        Ok(header) => header,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::RequestError,
+                format!(
+                    "[api.openid_connect.decode_token] invalid token header: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    let key_id = match header.kid {
+        Some(key_id) => key_id,
+        None => {
+            return Err(Error::new(
+                ErrorKind::RequestError,
+                "[api.openid_connect.decode_token] token header has no key id",
+            ))
+        }
+    };
+
+    let json_web_key = match json_web_key_set.find(&key_id) {
+        Some(json_web_key) => json_web_key,
+        None => {
+            return Err(Error::new(
+                ErrorKind::RequestError,
+                format!(
+                    "[api.openid_connect.decode_token] unknown token key id '{}'",
+                    &key_id
+                ),
+            ))
+        }
+    };
+
+    let decoding_key = match DecodingKey::from_jwk(json_web_key) {
+        Ok(decoding_key) => decoding_key,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::RequestError,
+                format!(
+                    "[api.openid_connect.decode_token] failed to build decoding key: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    match decode::<C>(token, &decoding_key, validation) {
+        Ok(token_data) => Ok(token_data.claims),
+        Err(error) => Err(Error::new(
+            ErrorKind::RequestError,
+            format!(
+                "[api.openid_connect.decode_token] invalid token: {}",
+                &error
+            ),
+        )),
+    }
+}
+
+#[async_trait]
+impl InputPlugin for OpenIdConnectPlugin {
+    fn id(&self) -> &str {
+        OPENID_CONNECT_PLUGIN_ID
+    }
+
+    async fn handle_request(&self, request: Request) -> Result<Request, Error> {
+        let user_id = self.authenticate(request.header().token()).await?;
+
+        let mut payload = request.payload().clone();
+
+        match payload.as_object_mut() {
+            Some(payload) => {
+                payload.insert(USER_ID_PAYLOAD_KEY.to_string(), Value::String(user_id));
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::RequestError,
+                    "[api.openid_connect.handle_request] payload is not an object",
+                ))
+            }
+        }
+
+        let header = RequestHeader::new(
+            request.header().action().to_string(),
+            request.header().token().to_string(),
+        );
+
+        Ok(Request::new(header, payload))
+    }
+}
+
+#[cfg(test)]
+use jsonwebtoken::{encode, EncodingKey, Header};
+#[cfg(test)]
+use serde::Serialize;
+
+#[cfg(test)]
+const TEST_ISSUER: &str = "https://cp-organization.test/";
+#[cfg(test)]
+const TEST_AUDIENCE: &str = "cp-organization";
+
+#[cfg(test)]
+#[derive(Serialize)]
+struct TestClaims {
+    sub: String,
+    iss: String,
+    aud: String,
+    exp: usize,
+}
+
+#[cfg(test)]
+fn create_test_plugin() -> OpenIdConnectPlugin {
+    let config = OpenIdConnectConfig {
+        issuer: TEST_ISSUER.to_string(),
+        audience: TEST_AUDIENCE.to_string(),
+        jwks_uri: None,
+        jwks_file: None,
+    };
+
+    let json_web_key_set: JwkSet = serde_json::from_str(include_str!(
+        "../../../test_fixtures/openid_connect/jwks.json"
+    ))
+    .unwrap();
+
+    OpenIdConnectPlugin::new(&config, Arc::new(JsonWebKeys::new(json_web_key_set, None)))
+}
+
+#[cfg(test)]
+fn create_test_token(issuer: &str) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some("cp-organization-test".to_string());
+
+    let claims = TestClaims {
+        sub: "auth0|gabriel".to_string(),
+        iss: issuer.to_string(),
+        aud: TEST_AUDIENCE.to_string(),
+        exp: 32503680000usize,
+    };
+
+    let encoding_key = EncodingKey::from_rsa_pem(include_bytes!(
+        "../../../test_fixtures/openid_connect/jwt_signing_key.pem"
+    ))
+    .unwrap();
+
+    encode(&header, &claims, &encoding_key).unwrap()
+}
+
+#[tokio::test]
+pub async fn overrides_user_id_with_token_subject() {
+    let plugin = create_test_plugin();
+
+    let request_header: RequestHeader =
+        RequestHeader::new("create_org".to_string(), create_test_token(TEST_ISSUER));
+    let request: Request =
+        Request::new(request_header, serde_json::json!({ "user_id": "impostor" }));
+
+    let request = plugin.handle_request(request).await.unwrap();
+
+    assert_eq!("auth0|gabriel", request.payload()[USER_ID_PAYLOAD_KEY]);
+}
+
+#[tokio::test]
+pub async fn error_when_token_is_not_a_jwt() {
+    let plugin = create_test_plugin();
+
+    let request_header: RequestHeader =
+        RequestHeader::new("create_org".to_string(), "1234abcd".to_string());
+    let request: Request = Request::new(request_header, serde_json::json!({}));
+
+    match plugin.handle_request(request).await {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::RequestError, error.kind),
+    }
+}
+
+#[tokio::test]
+pub async fn error_when_token_issuer_is_unexpected() {
+    let plugin = create_test_plugin();
+
+    let request_header: RequestHeader = RequestHeader::new(
+        "create_org".to_string(),
+        create_test_token("https://someone-else.test/"),
+    );
+    let request: Request = Request::new(request_header, serde_json::json!({}));
+
+    match plugin.handle_request(request).await {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::RequestError, error.kind),
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use cp_microservice::{
+    api::{server::input::input_plugin::InputPlugin, shared::request::Request},
+    core::error::{Error, ErrorKind},
+};
+use jsonwebtoken::Validation;
+use serde::Deserialize;
+
+use crate::api::{
+    json_web_keys::JsonWebKeys,
+    plugins::openid_connect::{decode_token, OpenIdConnectConfig},
+};
+
+pub const SERVICE_TOKEN_PLUGIN_ID: &str = "service_token";
+
+/// Scope granted to the client credentials of the services allowed to query this one.
+pub const SERVICE_SCOPE: &str = "organization:service";
+
+const SCOPE_SEPARATOR: char = ' ';
+
+#[derive(Deserialize)]
+struct ServiceClaims {
+    #[serde(default)]
+    scope: String,
+}
+
+/// Authenticates calling services through their client credentials access token, which must
+/// carry the service scope. Unlike the OpenID Connect plugin, the payload is left untouched
+/// because services act on behalf of arbitrary users.
+pub struct ServiceTokenPlugin {
+    json_web_keys: Arc<JsonWebKeys>,
+    validation: Validation,
+}
+
+impl ServiceTokenPlugin {
+    pub fn new(config: &OpenIdConnectConfig, json_web_keys: Arc<JsonWebKeys>) -> Self {
+        Self {
+            json_web_keys,
+            validation: config.validation(),
+        }
+    }
+}
+
+#[async_trait]
+impl InputPlugin for ServiceTokenPlugin {
+    fn id(&self) -> &str {
+        SERVICE_TOKEN_PLUGIN_ID
+    }
+
+    async fn handle_request(&self, request: Request) -> Result<Request, Error> {
+        let json_web_key_set = self.json_web_keys.for_token(request.header().token()).await;
+
+        let claims: ServiceClaims = decode_token(
+            &json_web_key_set,
+            &self.validation,
+            request.header().token(),
+        )?;
+
+        if !claims
+            .scope
+            .split(SCOPE_SEPARATOR)
+            .any(|scope| scope == SERVICE_SCOPE)
+        {
+            return Err(Error::new(
+                ErrorKind::RequestError,
+                format!(
+                    "[api.service_token.handle_request] token lacks the '{}' scope",
+                    SERVICE_SCOPE
+                ),
+            ));
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+use cp_microservice::api::shared::request_header::RequestHeader;
+#[cfg(test)]
+use jsonwebtoken::{encode, jwk::JwkSet, Algorithm, EncodingKey, Header};
+#[cfg(test)]
+use serde::Serialize;
+
+#[cfg(test)]
+#[derive(Serialize)]
+struct TestClaims {
+    sub: String,
+    iss: String,
+    aud: String,
+    exp: usize,
+    scope: String,
+}
+
+#[cfg(test)]
+fn create_test_plugin() -> ServiceTokenPlugin {
+    let config: OpenIdConnectConfig = serde_json::from_value(serde_json::json!({
+        "issuer": "https://cp-organization.test/",
+        "audience": "cp-organization"
+    }))
+    .unwrap();
+
+    let json_web_key_set: JwkSet = serde_json::from_str(include_str!(
+        "../../../test_fixtures/openid_connect/jwks.json"
+    ))
+    .unwrap();
+
+    ServiceTokenPlugin::new(&config, Arc::new(JsonWebKeys::new(json_web_key_set, None)))
+}
+
+#[cfg(test)]
+fn create_test_token(scope: &str) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some("cp-organization-test".to_string());
+
+    let claims = TestClaims {
+        sub: "permissions-service@clients".to_string(),
+        iss: "https://cp-organization.test/".to_string(),
+        aud: "cp-organization".to_string(),
+        exp: 32503680000usize,
+        scope: scope.to_string(),
+    };
+
+    let encoding_key = EncodingKey::from_rsa_pem(include_bytes!(
+        "../../../test_fixtures/openid_connect/jwt_signing_key.pem"
+    ))
+    .unwrap();
+
+    encode(&header, &claims, &encoding_key).unwrap()
+}
+
+#[tokio::test]
+pub async fn keeps_payload_user_id_when_scope_is_granted() {
+    let plugin = create_test_plugin();
+
+    let request_header: RequestHeader = RequestHeader::new(
+        "check_permission".to_string(),
+        create_test_token("openid organization:service"),
+    );
+    let request: Request =
+        Request::new(request_header, serde_json::json!({ "user_id": "gabriel" }));
+
+    let request = plugin.handle_request(request).await.unwrap();
+
+    assert_eq!("gabriel", request.payload()["user_id"]);
+}
+
+#[tokio::test]
+pub async fn error_when_scope_is_missing() {
+    let plugin = create_test_plugin();
+
+    let request_header: RequestHeader =
+        RequestHeader::new("check_permission".to_string(), create_test_token("openid"));
+    let request: Request =
+        Request::new(request_header, serde_json::json!({ "user_id": "gabriel" }));
+
+    match plugin.handle_request(request).await {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::RequestError, error.kind),
+    }
+}
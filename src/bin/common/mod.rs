@@ -0,0 +1,35 @@
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Serialize;
+
+const TEST_KEY_ID: &str = "cp-organization-test";
+const TEST_ISSUER: &str = "https://cp-organization.test/";
+const TEST_AUDIENCE: &str = "cp-organization";
+const TEST_EXPIRATION: usize = 32503680000usize;
+
+#[derive(Serialize)]
+struct Claims {
+    sub: String,
+    iss: String,
+    aud: String,
+    exp: usize,
+}
+
+/// Signs an access token for the given user with the key published in `test_fixtures/openid_connect/jwks.json`.
+pub fn create_test_token(user_id: &str) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(TEST_KEY_ID.to_string());
+
+    let claims = Claims {
+        sub: user_id.to_string(),
+        iss: TEST_ISSUER.to_string(),
+        aud: TEST_AUDIENCE.to_string(),
+        exp: TEST_EXPIRATION,
+    };
+
+    let encoding_key = EncodingKey::from_rsa_pem(include_bytes!(
+        "../../../test_fixtures/openid_connect/jwt_signing_key.pem"
+    ))
+    .unwrap();
+
+    encode(&header, &claims, &encoding_key).unwrap()
+}
@@ -0,0 +1,79 @@
+use std::io::{Error, ErrorKind};
+
+use cp_organization::{
+    init::{get_mongodb_client, get_secrets_manager},
+    storage::migrations::{get_migrations, migrator::Migrator},
+};
+use simple_logger::SimpleLogger;
+
+const USAGE: &str = "usage: migrate <status|run>";
+
+/// Inspects or applies the storage migrations without starting the service, connecting to the
+/// database configured through the same environment variables.
+#[tokio::main]
+pub async fn main() -> Result<(), Error> {
+    SimpleLogger::new().init().unwrap();
+
+    let command = match std::env::args().nth(1) {
+        Some(command) if command == "status" || command == "run" => command,
+        _ => return Err(Error::new(ErrorKind::InvalidInput, USAGE)),
+    };
+
+    let secrets_manager = get_secrets_manager()?;
+    let migrator = Migrator::new(get_mongodb_client(&secrets_manager)?, get_migrations());
+
+    if command == "run" {
+        run(&migrator).await
+    } else {
+        status(&migrator).await
+    }
+}
+
+async fn status(migrator: &Migrator) -> Result<(), Error> {
+    let applied_migrations = match migrator.applied_migrations().await {
+        Ok(applied_migrations) => applied_migrations,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("failed to get applied migrations: {}", &error),
+            ))
+        }
+    };
+
+    for migration in migrator.migrations() {
+        let state = match applied_migrations
+            .iter()
+            .find(|applied| applied.version() == migration.version() as i64)
+        {
+            Some(applied) => format!(
+                "applied at {}",
+                applied
+                    .applied_at()
+                    .try_to_rfc3339_string()
+                    .unwrap_or_default()
+            ),
+            None => "pending".to_string(),
+        };
+
+        println!("{:04} {} {}", migration.version(), migration.name(), state);
+    }
+
+    Ok(())
+}
+
+async fn run(migrator: &Migrator) -> Result<(), Error> {
+    match migrator.run_pending().await {
+        Ok(applied_versions) if applied_versions.is_empty() => {
+            println!("no pending migrations");
+            Ok(())
+        }
+        Ok(applied_versions) => {
+            println!("applied {} migrations", applied_versions.len());
+            Ok(())
+        }
+        Err(error) => Err(Error::new(
+            ErrorKind::Other,
+            format!("failed to run migrations: {}", &error),
+        )),
+    }
+}
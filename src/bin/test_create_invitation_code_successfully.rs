@@ -17,6 +17,8 @@ use multiple_connections_lapin_wrapper::{
 };
 use serde_json::{json, Value};
 
+mod common;
+
 #[tokio::main]
 pub async fn main() {
     let amqp_connection_uri = std::env::args()
@@ -90,13 +92,42 @@ pub async fn main() {
 
     let amqp_input_consumer: AmqpInputConsumer =
         AmqpInputConsumer::new(channel, publisher, 5000u64);
-    let mut request_header: RequestHeader =
-        RequestHeader::new("create_invitation_code".to_string(), "1234abcd".to_string());
+    let request_header: RequestHeader = RequestHeader::new(
+        "create_org".to_string(),
+        common::create_test_token("gabriel"),
+    );
+
+    let request: Request = Request::new(
+        request_header,
+        json!({
+            "country": "es",
+            "name": "example",
+            "address": {
+                "country": "es",
+                "region": "albacete",
+                "city": "villarrobledo",
+                "street": "calle molino estrada",
+                "number": "37",
+                "additional": "",
+                "postal_code": "02600"
+            }
+        }),
+    );
+
+    let response: Value = amqp_input_consumer.send_request(request).await.unwrap();
+    let response_object = serde_json::from_value::<Result<String, Error>>(response).unwrap();
+
+    let organization_id = response_object.unwrap();
+
+    let mut request_header: RequestHeader = RequestHeader::new(
+        "create_invitation_code".to_string(),
+        common::create_test_token("gabriel"),
+    );
 
     let request: Request = Request::new(
         request_header,
         json!({
-            "org_id": "1234",
+            "org_id": &organization_id,
             "permissions": ["abcd", "efgh"],
             "roles": ["653846b428c2649821284c60"]
         }),
@@ -106,7 +137,7 @@ pub async fn main() {
     println!("Response: {}", &response);
     let response_object = serde_json::from_value::<Result<String, Error>>(response).unwrap();
 
-    let organization_id = response_object.unwrap();
+    let code = response_object.unwrap();
 
-    assert!(organization_id.len() > 0);
+    assert!(code.len() > 0);
 }
@@ -17,6 +17,8 @@ use multiple_connections_lapin_wrapper::{
 };
 use serde_json::{json, Value};
 
+mod common;
+
 #[tokio::main]
 pub async fn main() {
     let amqp_connection_uri = std::env::args()
@@ -90,8 +92,10 @@ pub async fn main() {
 
     let amqp_input_consumer: AmqpInputConsumer =
         AmqpInputConsumer::new(channel, publisher, 5000u64);
-    let mut request_header: RequestHeader =
-        RequestHeader::new("create_org".to_string(), "1234abcd".to_string());
+    let mut request_header: RequestHeader = RequestHeader::new(
+        "create_org".to_string(),
+        common::create_test_token("gabriel"),
+    );
 
     let request: Request = Request::new(
         request_header,
@@ -106,8 +110,7 @@ pub async fn main() {
                 "number": "37",
                 "additional": "",
                 "postal_code": "02600"
-            },
-            "user_id": "gabriel"
+            }
         }),
     );
 
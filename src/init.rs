@@ -1,6 +1,7 @@
 use std::{
     io::{Error, ErrorKind},
     sync::Arc,
+    time::Duration,
 };
 
 use cp_microservice::{
@@ -10,12 +11,24 @@ use cp_microservice::{
         core::bitwarden_secrets_manager::BitwardenSecretsManager,
     },
 };
+use jsonwebtoken::jwk::JwkSet;
 use mongodb::{options::ClientOptions, Client};
 use multiple_connections_lapin_wrapper::config::amqp_connect_config::AmqpConnectConfig;
 
+use crate::api::plugins::openid_connect::OpenIdConnectConfig;
+
 const SECRETS_MANAGER_ACCESS_TOKEN_ENV: &str = "CP_ORGANIZATION_SECRETS_MANAGER_ACCESS_TOKEN";
 const AMQP_CONNECTION_CONFIG_SECRET_ENV: &str = "CP_ORGANIZATION_AMQP_CONNECTION_SECRET";
 const MONGODB_CONNECTION_CONFIG_SECRET_ENV: &str = "CP_ORGANIZATION_MONGODB_CONNECTION_SECRET";
+const MONGODB_TRANSACTIONS_ENABLED_ENV: &str = "CP_ORGANIZATION_MONGODB_TRANSACTIONS_ENABLED";
+const DELETED_ORGANIZATION_GRACE_PERIOD_ENV: &str =
+    "CP_ORGANIZATION_DELETED_ORGANIZATION_GRACE_PERIOD_IN_SECONDS";
+const DEFAULT_ADMIN_PERMISSIONS_ENV: &str = "CP_ORGANIZATION_DEFAULT_ADMIN_PERMISSIONS";
+const DEFAULT_MEMBER_PERMISSIONS_ENV: &str = "CP_ORGANIZATION_DEFAULT_MEMBER_PERMISSIONS";
+
+const DEFAULT_DELETED_ORGANIZATION_GRACE_PERIOD_IN_SECONDS: u64 = 2592000u64;
+const DEFAULT_ADMIN_PERMISSIONS: &[&str] = &["*"];
+const DEFAULT_MEMBER_PERMISSIONS: &[&str] = &["org:read", "org:members:read", "org:roles:read"];
 
 pub fn get_secrets_manager() -> Result<Arc<dyn SecretsManager>, Error> {
     let access_token = match std::env::var(SECRETS_MANAGER_ACCESS_TOKEN_ENV) {
@@ -158,3 +171,175 @@ pub fn get_mongodb_client(secrets_manager: &Arc<dyn SecretsManager>) -> Result<C
 
     Ok(mongodb_client)
 }
+
+pub fn get_deleted_organization_grace_period() -> Result<Duration, Error> {
+    let grace_period = match std::env::var(DELETED_ORGANIZATION_GRACE_PERIOD_ENV) {
+        Ok(grace_period) => grace_period,
+        Err(_) => {
+            return Ok(Duration::from_secs(
+                DEFAULT_DELETED_ORGANIZATION_GRACE_PERIOD_IN_SECONDS,
+            ))
+        }
+    };
+
+    match grace_period.parse::<u64>() {
+        Ok(grace_period) => Ok(Duration::from_secs(grace_period)),
+        Err(error) => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "invalid value for '{}': {}",
+                DELETED_ORGANIZATION_GRACE_PERIOD_ENV, &error
+            ),
+        )),
+    }
+}
+
+/// Multi-document writes run within transactions unless disabled, which they must be for
+/// standalone MongoDB servers as transactions need a replica set. The development and CI
+/// environments run standalone servers, so they use the saga fallback of every such write.
+pub fn get_mongodb_transactions_enabled() -> Result<bool, Error> {
+    let transactions_enabled = match std::env::var(MONGODB_TRANSACTIONS_ENABLED_ENV) {
+        Ok(transactions_enabled) => transactions_enabled,
+        Err(_) => return Ok(true),
+    };
+
+    match transactions_enabled.parse::<bool>() {
+        Ok(transactions_enabled) => Ok(transactions_enabled),
+        Err(error) => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "invalid value for '{}': {}",
+                MONGODB_TRANSACTIONS_ENABLED_ENV, &error
+            ),
+        )),
+    }
+}
+
+pub fn get_openid_connect_config() -> Result<OpenIdConnectConfig, Error> {
+    let openid_connect_config_file = match std::env::args().nth(2) {
+        Some(openid_connect_config_file) => openid_connect_config_file,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "no openid connect config file provided",
+            ));
+        }
+    };
+
+    let openid_connect_config_file_content =
+        match std::fs::read_to_string(&openid_connect_config_file) {
+            Ok(content) => content,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "failed to find openid connect config file '{}': {}",
+                        &openid_connect_config_file, &error
+                    ),
+                ))
+            }
+        };
+
+    match serde_json::from_str::<OpenIdConnectConfig>(&openid_connect_config_file_content) {
+        Ok(openid_connect_config) => Ok(openid_connect_config),
+        Err(error) => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "failed to deserialize openid connect config file: {}",
+                &error
+            ),
+        )),
+    }
+}
+
+pub async fn get_json_web_key_set(
+    openid_connect_config: &OpenIdConnectConfig,
+) -> Result<JwkSet, Error> {
+    match (
+        openid_connect_config.jwks_file(),
+        openid_connect_config.jwks_uri(),
+    ) {
+        (Some(jwks_file), _) => match std::fs::read_to_string(jwks_file) {
+            Ok(content) => deserialize_json_web_key_set(&content),
+            Err(error) => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("failed to find jwks file '{}': {}", jwks_file, &error),
+            )),
+        },
+        (None, Some(jwks_uri)) => fetch_json_web_key_set(jwks_uri).await,
+        (None, None) => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "openid connect config provides neither 'jwks_file' nor 'jwks_uri'",
+        )),
+    }
+}
+
+/// Downloads the key set published by the identity provider, which is also used to refetch it
+/// once tokens are signed by a rotated key.
+pub async fn fetch_json_web_key_set(jwks_uri: &str) -> Result<JwkSet, Error> {
+    let content = match reqwest::get(jwks_uri).await {
+        Ok(response) => match response.text().await {
+            Ok(content) => content,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("failed to read jwks from '{}': {}", jwks_uri, &error),
+                ))
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::NotConnected,
+                format!("failed to fetch jwks from '{}': {}", jwks_uri, &error),
+            ))
+        }
+    };
+
+    deserialize_json_web_key_set(&content)
+}
+
+fn deserialize_json_web_key_set(content: &str) -> Result<JwkSet, Error> {
+    match serde_json::from_str::<JwkSet>(content) {
+        Ok(json_web_key_set) => Ok(json_web_key_set),
+        Err(error) => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to deserialize jwks: {}", &error),
+        )),
+    }
+}
+
+pub fn get_default_admin_permissions() -> Result<Vec<String>, Error> {
+    get_permissions(DEFAULT_ADMIN_PERMISSIONS_ENV, DEFAULT_ADMIN_PERMISSIONS)
+}
+
+pub fn get_default_member_permissions() -> Result<Vec<String>, Error> {
+    get_permissions(DEFAULT_MEMBER_PERMISSIONS_ENV, DEFAULT_MEMBER_PERMISSIONS)
+}
+
+/// Reads a comma separated permission list from the environment variable, falling back to the
+/// given permissions when it is not set.
+fn get_permissions(env: &str, default_permissions: &[&str]) -> Result<Vec<String>, Error> {
+    let permissions = match std::env::var(env) {
+        Ok(permissions) => permissions,
+        Err(_) => {
+            return Ok(default_permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect())
+        }
+    };
+
+    let permissions: Vec<String> = permissions
+        .split(',')
+        .map(|permission| permission.trim().to_string())
+        .collect();
+
+    if permissions.iter().any(|permission| permission.is_empty()) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("invalid value for '{}': empty permission", env),
+        ));
+    }
+
+    Ok(permissions)
+}
@@ -0,0 +1,5 @@
+pub mod api;
+pub mod error;
+pub mod init;
+pub mod logic;
+pub mod storage;
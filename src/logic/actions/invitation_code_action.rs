@@ -1,12 +1,34 @@
 use cp_microservice::core::error::Error;
 use tokio::sync::oneshot::Sender;
 
+use crate::storage::invitation_code::InvitationCode;
+
 #[derive(Debug)]
 pub enum InvitationCodeAction {
     Create {
         org_id: String,
         permissions: Vec<String>,
+        denied_permissions: Vec<String>,
         roles: Vec<String>,
+        expires_at: Option<String>,
+        max_uses: Option<u32>,
+        user_id: String,
+        replier: Sender<Result<String, Error>>,
+    },
+    Redeem {
+        code: String,
+        user_id: String,
         replier: Sender<Result<String, Error>>,
     },
+    List {
+        org_id: String,
+        user_id: String,
+        replier: Sender<Result<Vec<InvitationCode>, Error>>,
+    },
+    Revoke {
+        org_id: String,
+        code: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
 }
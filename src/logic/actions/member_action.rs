@@ -0,0 +1,56 @@
+use cp_microservice::core::error::Error;
+use tokio::sync::oneshot::Sender;
+
+use crate::storage::{member_page::MemberPage, user_organization::UserOrganization};
+
+#[derive(Debug)]
+pub enum MemberAction {
+    ListUserOrganizations {
+        user_id: String,
+        replier: Sender<Result<Vec<UserOrganization>, Error>>,
+    },
+    List {
+        org_id: String,
+        user_id: String,
+        role_id: Option<String>,
+        sort_by: Option<String>,
+        descending: Option<bool>,
+        cursor: Option<String>,
+        limit: Option<u32>,
+        replier: Sender<Result<MemberPage, Error>>,
+    },
+    UpdateRoles {
+        org_id: String,
+        user_id: String,
+        member_user_id: String,
+        roles: Option<Vec<String>>,
+        add: Vec<String>,
+        remove: Vec<String>,
+        replier: Sender<Result<(), Error>>,
+    },
+    UpdatePermissions {
+        org_id: String,
+        user_id: String,
+        member_user_id: String,
+        permissions: Option<Vec<String>>,
+        denied_permissions: Option<Vec<String>>,
+        replier: Sender<Result<(), Error>>,
+    },
+    Remove {
+        org_id: String,
+        user_id: String,
+        member_user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Leave {
+        org_id: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    CheckPermission {
+        user_id: String,
+        org_id: String,
+        permission: String,
+        replier: Sender<Result<bool, Error>>,
+    },
+}
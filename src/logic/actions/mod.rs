@@ -1,2 +1,4 @@
 pub mod invitation_code_action;
+pub mod member_action;
 pub mod organization_action;
+pub mod role_action;
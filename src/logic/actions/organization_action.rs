@@ -2,6 +2,8 @@ use cp_core::geolocalization::address::Address;
 use cp_microservice::core::error::Error;
 use tokio::sync::oneshot::Sender;
 
+use crate::storage::organization::Organization;
+
 #[derive(Debug)]
 pub enum OrganizationAction {
     Create {
@@ -11,4 +13,34 @@ pub enum OrganizationAction {
         user_id: String,
         replier: Sender<Result<String, Error>>,
     },
+    Get {
+        id: String,
+        user_id: String,
+        replier: Sender<Result<Organization, Error>>,
+    },
+    Update {
+        id: String,
+        country: Option<String>,
+        name: Option<String>,
+        address: Option<Address>,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Delete {
+        id: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Restore {
+        id: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    TransferOwnership {
+        id: String,
+        user_id: String,
+        new_owner_id: String,
+        demote_previous_owner: bool,
+        replier: Sender<Result<(), Error>>,
+    },
 }
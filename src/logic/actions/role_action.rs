@@ -0,0 +1,36 @@
+use cp_microservice::core::error::Error;
+use tokio::sync::oneshot::Sender;
+
+use crate::storage::role::Role;
+
+#[derive(Debug)]
+pub enum RoleAction {
+    Create {
+        org_id: String,
+        name: String,
+        permissions: Vec<String>,
+        parents: Vec<String>,
+        user_id: String,
+        replier: Sender<Result<String, Error>>,
+    },
+    Update {
+        id: String,
+        org_id: String,
+        name: Option<String>,
+        permissions: Option<Vec<String>>,
+        parents: Option<Vec<String>>,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Delete {
+        id: String,
+        org_id: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    List {
+        org_id: String,
+        user_id: String,
+        replier: Sender<Result<Vec<Role>, Error>>,
+    },
+}
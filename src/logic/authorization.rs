@@ -0,0 +1,219 @@
+use async_channel::Sender;
+use bson::oid::ObjectId;
+use cp_microservice::core::error::{Error, ErrorKind};
+
+use crate::{
+    logic::{
+        executors::{
+            member::get_member, organization::get_organization, role::list_organization_roles,
+        },
+        permission::EffectivePermissions,
+        role_hierarchy::collect_permissions,
+    },
+    storage::storage_request::StorageRequest,
+};
+
+pub const PERMISSION_READ_ORGANIZATION: &str = "org:read";
+pub const PERMISSION_UPDATE_ORGANIZATION: &str = "org:update";
+pub const PERMISSION_DELETE_ORGANIZATION: &str = "org:delete";
+pub const PERMISSION_RESTORE_ORGANIZATION: &str = "org:restore";
+pub const PERMISSION_TRANSFER_OWNERSHIP: &str = "org:ownership:transfer";
+pub const PERMISSION_INVITE: &str = "org:invite";
+pub const PERMISSION_READ_INVITATION_CODES: &str = "org:invitations:read";
+pub const PERMISSION_REVOKE_INVITATION_CODES: &str = "org:invitations:revoke";
+pub const PERMISSION_READ_MEMBERS: &str = "org:members:read";
+pub const PERMISSION_UPDATE_MEMBERS: &str = "org:members:update";
+pub const PERMISSION_UPDATE_MEMBER_PERMISSIONS: &str = "org:members:permissions:update";
+pub const PERMISSION_REMOVE_MEMBERS: &str = "org:members:remove";
+pub const PERMISSION_READ_ROLES: &str = "org:roles:read";
+pub const PERMISSION_CREATE_ROLES: &str = "org:roles:create";
+pub const PERMISSION_UPDATE_ROLES: &str = "org:roles:update";
+pub const PERMISSION_DELETE_ROLES: &str = "org:roles:delete";
+
+/// Resolves the permissions the user holds within the organization, which are the ones of its
+/// roles and their ancestors plus the ones granted to the member directly, minus the ones
+/// denied to the member. Returns `None` when the user is not a member of the organization or
+/// the organization does not exist, soft deleted ones included.
+pub async fn get_effective_permissions<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    organization_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<T, Error>>,
+        Option<EffectivePermissions>,
+    ),
+    Error,
+> {
+    if ObjectId::parse_str(&organization_id).is_err() {
+        return Ok((api_replier, None));
+    }
+
+    let (api_replier, organization) =
+        get_organization(sender, organization_id.clone(), api_replier).await?;
+
+    if organization.is_none() {
+        return Ok((api_replier, None));
+    }
+
+    get_member_permissions(sender, user_id, organization_id, api_replier).await
+}
+
+/// Resolves the permissions of the member regardless of whether the organization was deleted.
+async fn get_member_permissions<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    organization_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<T, Error>>,
+        Option<EffectivePermissions>,
+    ),
+    Error,
+> {
+    let (api_replier, member) =
+        get_member(sender, user_id, organization_id.clone(), api_replier).await?;
+
+    let member = match member {
+        Some(member) => member,
+        None => return Ok((api_replier, None)),
+    };
+
+    let (api_replier, roles) =
+        list_organization_roles(sender, organization_id, api_replier).await?;
+
+    let mut granted: Vec<String> = member.permissions().to_vec();
+    granted.extend(collect_permissions(&roles, member.roles()));
+
+    let permissions = EffectivePermissions::new(granted, member.denied_permissions().to_vec());
+
+    Ok((api_replier, Some(permissions)))
+}
+
+/// Rejects the request unless the user is a member of the organization holding the permission.
+pub async fn authorize<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: &str,
+    organization_id: &str,
+    permission: &str,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    let (api_replier, permissions) = get_effective_permissions(
+        sender,
+        user_id.to_string(),
+        organization_id.to_string(),
+        api_replier,
+    )
+    .await?;
+
+    reply_authorization(
+        permissions,
+        user_id,
+        organization_id,
+        permission,
+        api_replier,
+    )
+}
+
+/// Rejects the request unless the user is a member of the organization holding the permission,
+/// even if the organization was soft deleted. Restoring is the only action allowed on those.
+pub async fn authorize_deleted<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: &str,
+    organization_id: &str,
+    permission: &str,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    let (api_replier, permissions) = get_member_permissions(
+        sender,
+        user_id.to_string(),
+        organization_id.to_string(),
+        api_replier,
+    )
+    .await?;
+
+    reply_authorization(
+        permissions,
+        user_id,
+        organization_id,
+        permission,
+        api_replier,
+    )
+}
+
+fn reply_authorization<T>(
+    permissions: Option<EffectivePermissions>,
+    user_id: &str,
+    organization_id: &str,
+    permission: &str,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    let message = match permissions {
+        Some(permissions) if permissions.allows(permission) => return Ok(api_replier),
+        Some(_) => format!(
+            "[logic.authorization.authorize] user '{}' lacks permission '{}' in organization '{}'",
+            user_id, permission, organization_id
+        ),
+        None => format!(
+            "[logic.authorization.authorize] organization '{}' not found or user '{}' is not a member of it",
+            organization_id, user_id
+        ),
+    };
+
+    let error = Error::new(ErrorKind::LogicError, message);
+
+    if let Err(_) = api_replier.send(Err(error.clone())) {
+        log::warn!("failed to reply to api with an error");
+    }
+
+    Err(error)
+}
+
+/// Rejects the request unless the user holds every one of the `granted` permissions, so members
+/// can only hand out permissions they hold themselves.
+pub async fn authorize_grants<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: &str,
+    organization_id: &str,
+    granted: &[String],
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    if granted.is_empty() {
+        return Ok(api_replier);
+    }
+
+    let (api_replier, permissions) = get_effective_permissions(
+        sender,
+        user_id.to_string(),
+        organization_id.to_string(),
+        api_replier,
+    )
+    .await?;
+
+    let message = match permissions {
+        Some(permissions) => match granted
+            .iter()
+            .find(|&permission| !permissions.allows(permission))
+        {
+            Some(permission) => format!(
+                "[logic.authorization.authorize_grants] user '{}' cannot grant permission '{}' without holding it in organization '{}'",
+                user_id, permission, organization_id
+            ),
+            None => return Ok(api_replier),
+        },
+        None => format!(
+            "[logic.authorization.authorize_grants] organization '{}' not found or user '{}' is not a member of it",
+            organization_id, user_id
+        ),
+    };
+
+    let error = Error::new(ErrorKind::LogicError, message);
+
+    if let Err(_) = api_replier.send(Err(error.clone())) {
+        log::warn!("failed to reply to api with an error");
+    }
+
+    Err(error)
+}
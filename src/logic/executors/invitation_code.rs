@@ -1,4 +1,5 @@
 use async_channel::Sender;
+use bson::{oid::ObjectId, DateTime};
 use cp_microservice::{
     core::error::{Error, ErrorKind},
     logic::executor::{timeout_receive_storage_response, timeout_send_storage_request},
@@ -6,13 +7,33 @@ use cp_microservice::{
 use uuid::Uuid;
 
 use crate::{
-    logic::{actions::invitation_code_action::InvitationCodeAction, logic_request::LogicRequest},
-    storage::{self, storage_request::StorageRequest},
+    logic::{
+        actions::invitation_code_action::InvitationCodeAction,
+        authorization::{
+            authorize, authorize_grants, PERMISSION_INVITE, PERMISSION_READ_INVITATION_CODES,
+            PERMISSION_REVOKE_INVITATION_CODES,
+        },
+        executors::{
+            organization::get_organization,
+            role::{get_roles, list_organization_roles},
+        },
+        logic_request::LogicRequest,
+        role_hierarchy::collect_permissions,
+        saga::Saga,
+    },
+    storage::{
+        self, invitation_code::InvitationCode, storage_request::StorageRequest,
+        unique_write::UniqueWrite,
+    },
 };
 
 const TIMEOUT_CREATE_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
+
+const TIMEOUT_LIST_INVITATION_CODES_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_REVOKE_INVITATION_CODE_IN_MILLISECONDS: u64 = 10000u64;
 
-pub async fn create_invitation_code(
+pub async fn execute_invitation_code_action(
     request: LogicRequest,
     sender: Sender<StorageRequest>,
 ) -> Result<(), Error> {
@@ -22,16 +43,48 @@ pub async fn create_invitation_code(
                 InvitationCodeAction::Create {
                     org_id,
                     permissions,
+                    denied_permissions,
                     roles,
+                    expires_at,
+                    max_uses,
+                    user_id,
+                    replier,
+                } => {
+                    handle_create_invitation_code(
+                        &sender,
+                        org_id,
+                        permissions,
+                        denied_permissions,
+                        roles,
+                        expires_at,
+                        max_uses,
+                        user_id,
+                        replier,
+                    )
+                    .await
+                }
+                InvitationCodeAction::Redeem {
+                    code,
+                    user_id,
+                    replier,
+                } => handle_redeem_invitation_code(&sender, code, user_id, replier).await,
+                InvitationCodeAction::List {
+                    org_id,
+                    user_id,
+                    replier,
+                } => handle_list_invitation_codes(&sender, org_id, user_id, replier).await,
+                InvitationCodeAction::Revoke {
+                    org_id,
+                    code,
+                    user_id,
                     replier,
                 } => {
-                    handle_create_invitation_code(&sender, org_id, permissions, roles, replier).await
+                    handle_revoke_invitation_code(&sender, org_id, code, user_id, replier).await
                 }
-                _ => Err(Error::new(ErrorKind::LogicError, "[logic.invitation_code.create_invitation_code] received an unexpected invitation code action")),
             },
-            None => Err(Error::new(ErrorKind::LogicError, "[logic.invitation_code.create_invitation_code] received 'None' as invitation code action")),
+            None => Err(Error::new(ErrorKind::LogicError, "[logic.invitation_code.execute_invitation_code_action] received 'None' as invitation code action")),
         },
-        _ => Err(Error::new(ErrorKind::LogicError, "[logic.invitation_code.create_invitation_code] received an unexpected logic request")),
+        _ => Err(Error::new(ErrorKind::LogicError, "[logic.invitation_code.execute_invitation_code_action] received an unexpected logic request")),
     }
 }
 
@@ -39,9 +92,46 @@ async fn handle_create_invitation_code(
     sender: &Sender<StorageRequest>,
     org_id: String,
     permissions: Vec<String>,
+    denied_permissions: Vec<String>,
     roles: Vec<String>,
-    mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+    expires_at: Option<String>,
+    max_uses: Option<u32>,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
 ) -> Result<(), Error> {
+    if ObjectId::parse_str(&org_id).is_err() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.invitation_code.handle_create_invitation_code] invalid organization id '{}'",
+                &org_id
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (api_replier, expires_at, max_uses) =
+        validate_invitation_code_limits(api_replier, expires_at, max_uses)?;
+
+    let api_replier = authorize(sender, &user_id, &org_id, PERMISSION_INVITE, api_replier).await?;
+
+    let api_replier = validate_invitation_code_target(sender, &org_id, &roles, api_replier).await?;
+
+    let mut api_replier = authorize_invitation_code_grants(
+        sender,
+        &user_id,
+        &org_id,
+        &permissions,
+        &roles,
+        api_replier,
+    )
+    .await?;
+
     let code = Uuid::new_v4().to_string();
 
     let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
@@ -51,7 +141,10 @@ async fn handle_create_invitation_code(
             code: code.clone(),
             org_id,
             permissions,
+            denied_permissions,
             roles,
+            expires_at,
+            max_uses,
             replier: storage_replier,
         },
     ));
@@ -64,16 +157,844 @@ async fn handle_create_invitation_code(
     )
     .await?;
 
-    let (api_replier, _) = timeout_receive_storage_response(
+    let (api_replier, write) = timeout_receive_storage_response(
         TIMEOUT_CREATE_INVITATION_CODE_IN_MILLISECONDS,
         storage_receiver,
         api_replier,
     )
     .await?;
 
+    if write == UniqueWrite::Duplicate {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.invitation_code.handle_create_invitation_code] generated invitation code is already in use",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
     if let Err(_) = api_replier.send(Ok(code)) {
         log::warn!("failed to reply to api with an ok");
     }
 
     Ok(())
 }
+
+async fn validate_invitation_code_target(
+    sender: &Sender<StorageRequest>,
+    org_id: &String,
+    roles: &Vec<String>,
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
+    let (api_replier, organization) = get_organization(sender, org_id.clone(), api_replier).await?;
+
+    if organization.is_none() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.invitation_code.validate_invitation_code_target] organization '{}' does not exist",
+                org_id
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    if roles.is_empty() {
+        return Ok(api_replier);
+    }
+
+    let (api_replier, existing_roles) = get_roles(sender, roles.clone(), api_replier).await?;
+
+    for role_id in roles {
+        let message = match existing_roles.iter().find(|role| &role.id() == role_id) {
+            Some(role) if role.is_available_to(org_id) => continue,
+            Some(_) => format!(
+                "[logic.invitation_code.validate_invitation_code_target] role '{}' does not belong to organization '{}'",
+                role_id, org_id
+            ),
+            None => format!(
+                "[logic.invitation_code.validate_invitation_code_target] role '{}' does not exist",
+                role_id
+            ),
+        };
+
+        let error = Error::new(ErrorKind::LogicError, message);
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    Ok(api_replier)
+}
+
+/// Members joining through the code are granted its permissions and every permission of its
+/// roles, all of which the inviter must hold. Roles are checked by their permissions rather than
+/// by membership, as admins do not hold the member role themselves.
+async fn authorize_invitation_code_grants(
+    sender: &Sender<StorageRequest>,
+    user_id: &str,
+    org_id: &str,
+    permissions: &[String],
+    roles: &[String],
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
+    let mut granted: Vec<String> = permissions.to_vec();
+
+    let api_replier = if roles.is_empty() {
+        api_replier
+    } else {
+        let (api_replier, organization_roles) =
+            list_organization_roles(sender, org_id.to_string(), api_replier).await?;
+
+        granted.extend(collect_permissions(&organization_roles, roles));
+
+        api_replier
+    };
+
+    authorize_grants(sender, user_id, org_id, &granted, api_replier).await
+}
+
+fn validate_invitation_code_limits(
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+    expires_at: Option<String>,
+    max_uses: Option<u32>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<String, Error>>,
+        Option<DateTime>,
+        Option<u32>,
+    ),
+    Error,
+> {
+    let expires_at = match expires_at {
+        Some(expires_at) => match DateTime::parse_rfc3339_str(&expires_at) {
+            Ok(expires_at) if expires_at > DateTime::now() => Some(expires_at),
+            Ok(_) => {
+                let error = Error::new(
+                    ErrorKind::LogicError,
+                    "[logic.invitation_code.validate_invitation_code_limits] expiration date is in the past",
+                );
+
+                if let Err(_) = api_replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to api with an error");
+                }
+
+                return Err(error);
+            }
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::LogicError,
+                    format!("[logic.invitation_code.validate_invitation_code_limits] invalid expiration date: {}", &error),
+                );
+
+                if let Err(_) = api_replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to api with an error");
+                }
+
+                return Err(error);
+            }
+        },
+        None => None,
+    };
+
+    // Codes without a limit may be redeemed any number of times until they expire.
+    if max_uses == Some(0) {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.invitation_code.validate_invitation_code_limits] max uses must be greater than zero",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    Ok((api_replier, expires_at, max_uses))
+}
+
+async fn handle_redeem_invitation_code(
+    sender: &Sender<StorageRequest>,
+    code: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<(), Error> {
+    if code.is_empty() || user_id.is_empty() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.invitation_code.handle_redeem_invitation_code] code and user id must not be empty",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let mut saga = Saga::new(sender);
+    let redemption_id = Uuid::new_v4().to_string();
+
+    let (compensated_code, compensated_redemption_id) = (code.clone(), redemption_id.clone());
+    saga.register_compensation(move |replier| {
+        StorageRequest::InvitationCode(Some(
+            storage::actions::invitation_code_action::InvitationCodeAction::Release {
+                code: compensated_code,
+                redemption_id: compensated_redemption_id,
+                replier,
+            },
+        ))
+    });
+
+    let (api_replier, invitation_code) = saga
+        .step(consume_invitation_code(
+            sender,
+            code,
+            user_id.clone(),
+            redemption_id,
+            api_replier,
+        ))
+        .await?;
+
+    let invitation_code = match invitation_code {
+        Some(invitation_code) => invitation_code,
+        None => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.invitation_code.handle_redeem_invitation_code] invitation code does not exist, has expired or has no uses left",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let organization_id = invitation_code.org_id().to_string();
+
+    let (api_replier, organization) = saga
+        .step(get_organization(
+            sender,
+            organization_id.clone(),
+            api_replier,
+        ))
+        .await?;
+
+    if organization.is_none() {
+        saga.compensate().await;
+
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.invitation_code.handle_redeem_invitation_code] organization '{}' not found",
+                &organization_id
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (api_replier, roles) = if invitation_code.roles().is_empty() {
+        let (api_replier, member_role_id) =
+            saga.step(get_member_role_id(sender, api_replier)).await?;
+
+        (api_replier, vec![member_role_id])
+    } else {
+        (api_replier, invitation_code.roles().to_vec())
+    };
+
+    let api_replier = saga
+        .step(join_member(
+            sender,
+            user_id,
+            organization_id.clone(),
+            roles,
+            invitation_code.permissions().to_vec(),
+            invitation_code.denied_permissions().to_vec(),
+            api_replier,
+        ))
+        .await?;
+
+    if let Err(_) = api_replier.send(Ok(organization_id)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_list_invitation_codes(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<Vec<InvitationCode>, Error>>,
+) -> Result<(), Error> {
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_READ_INVITATION_CODES,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, invitation_codes) =
+        list_organization_invitation_codes(sender, org_id, api_replier).await?;
+
+    if let Err(_) = api_replier.send(Ok(invitation_codes)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn list_organization_invitation_codes<T>(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<T, Error>>,
+        Vec<InvitationCode>,
+    ),
+    Error,
+> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<Vec<InvitationCode>, Error>>();
+
+    let storage_request = StorageRequest::InvitationCode(Some(
+        storage::actions::invitation_code_action::InvitationCodeAction::ListByOrganization {
+            org_id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_LIST_INVITATION_CODES_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, invitation_codes) = timeout_receive_storage_response(
+        TIMEOUT_LIST_INVITATION_CODES_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, invitation_codes))
+}
+
+async fn handle_revoke_invitation_code(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    code: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let mut api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_REVOKE_INVITATION_CODES,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<bool, Error>>();
+
+    let storage_request = StorageRequest::InvitationCode(Some(
+        storage::actions::invitation_code_action::InvitationCodeAction::Revoke {
+            org_id,
+            code,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_REVOKE_INVITATION_CODE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, found) = timeout_receive_storage_response(
+        TIMEOUT_REVOKE_INVITATION_CODE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if !found {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.invitation_code.handle_revoke_invitation_code] invitation code not found",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn consume_invitation_code(
+    sender: &Sender<StorageRequest>,
+    code: String,
+    user_id: String,
+    redemption_id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<String, Error>>,
+        Option<InvitationCode>,
+    ),
+    Error,
+> {
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
+
+    let storage_request = StorageRequest::InvitationCode(Some(
+        storage::actions::invitation_code_action::InvitationCodeAction::Consume {
+            code,
+            user_id,
+            redemption_id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, invitation_code) = timeout_receive_storage_response(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, invitation_code))
+}
+
+async fn get_member_role_id(
+    sender: &Sender<StorageRequest>,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<(tokio::sync::oneshot::Sender<Result<String, Error>>, String), Error> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<String, Error>>();
+
+    let storage_request = StorageRequest::Role(Some(
+        storage::actions::role_action::RoleAction::GetMemberRoleId {
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member_role_id) = timeout_receive_storage_response(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, member_role_id))
+}
+
+async fn join_member(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    organization_id: String,
+    roles: Vec<String>,
+    permissions: Vec<String>,
+    denied_permissions: Vec<String>,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<UniqueWrite<()>, Error>>();
+
+    let storage_request =
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Join {
+            user_id,
+            organization_id,
+            roles,
+            permissions,
+            denied_permissions,
+            replier: storage_replier,
+        }));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, write) = timeout_receive_storage_response(
+        TIMEOUT_REDEEM_INVITATION_CODE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if write == UniqueWrite::Duplicate {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.invitation_code.join_member] user is already a member of the organization",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    Ok(api_replier)
+}
+
+#[cfg(test)]
+use crate::logic::test_storage::{
+    create_test_member, create_test_organization, create_test_roles, spawn_storage,
+    TEST_ADMIN_ROLE_ID, TEST_MEMBER_ROLE_ID, TEST_ORGANIZATION_ID,
+};
+#[cfg(test)]
+use crate::storage::actions::{
+    invitation_code_action::InvitationCodeAction as StorageInvitationCodeAction,
+    member_action::MemberAction, organization_action::OrganizationAction, role_action::RoleAction,
+};
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+#[cfg(test)]
+const TEST_FOREIGN_ROLE_ID: &str = "653846b428c2649821284c5f";
+
+/// Answers the creation of invitation codes by 'inviter', who may invite and read the
+/// organization but holds no other permission, recording whether any code was stored. Only the
+/// test organization exists, and a role of another organization is stored too.
+#[cfg(test)]
+fn spawn_invitation_storage(created: Arc<AtomicBool>) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Member(Some(MemberAction::Get {
+            user_id, replier, ..
+        })) => {
+            replier
+                .send(Ok(Some(create_test_member(
+                    &user_id,
+                    vec![TEST_MEMBER_ROLE_ID],
+                    vec!["org:invite"],
+                    vec![],
+                ))))
+                .unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+            replier.send(Ok(create_test_roles())).unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::GetByIds { ids, replier })) => {
+            let mut roles = create_test_roles();
+            roles.push(
+                bson::from_document(bson::doc! {
+                    "_id": ObjectId::parse_str(TEST_FOREIGN_ROLE_ID).unwrap(),
+                    "organization_id": "653846b428c2649821284c60",
+                    "name": "foreign",
+                    "permissions": []
+                })
+                .unwrap(),
+            );
+
+            let roles = roles
+                .into_iter()
+                .filter(|role| ids.contains(&role.id()))
+                .collect();
+
+            replier.send(Ok(roles)).unwrap();
+        }
+        StorageRequest::Organization(Some(OrganizationAction::Get { id, replier })) => {
+            let organization = if id == TEST_ORGANIZATION_ID {
+                Some(create_test_organization("gabriel"))
+            } else {
+                None
+            };
+
+            replier.send(Ok(organization)).unwrap();
+        }
+        StorageRequest::InvitationCode(Some(StorageInvitationCodeAction::Create {
+            code,
+            replier,
+            ..
+        })) => {
+            created.store(true, Ordering::SeqCst);
+            replier.send(Ok(UniqueWrite::Written(code))).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn create_invitation_code(
+    permissions: Vec<&str>,
+    roles: Vec<&str>,
+) -> (Result<String, Error>, bool) {
+    create_invitation_code_in(TEST_ORGANIZATION_ID, permissions, roles).await
+}
+
+#[cfg(test)]
+async fn create_invitation_code_in(
+    org_id: &str,
+    permissions: Vec<&str>,
+    roles: Vec<&str>,
+) -> (Result<String, Error>, bool) {
+    let created = Arc::new(AtomicBool::new(false));
+    let sender = spawn_invitation_storage(created.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_create_invitation_code(
+        &sender,
+        org_id.to_string(),
+        permissions.into_iter().map(String::from).collect(),
+        Vec::new(),
+        roles.into_iter().map(String::from).collect(),
+        None,
+        None,
+        "inviter".to_string(),
+        api_replier,
+    )
+    .await;
+
+    (api_receiver.await.unwrap(), created.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_grants_held_permissions_and_roles() {
+    let (result, created) =
+        create_invitation_code(vec!["org:read"], vec![TEST_MEMBER_ROLE_ID]).await;
+
+    assert!(result.is_ok());
+    assert!(created);
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_refuses_permission_not_held_by_inviter() {
+    let (result, created) = create_invitation_code(vec!["org:delete"], vec![]).await;
+
+    assert!(result.is_err());
+    assert!(!created);
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_refuses_role_with_permissions_not_held_by_inviter() {
+    let (result, created) = create_invitation_code(vec![], vec![TEST_ADMIN_ROLE_ID]).await;
+
+    assert!(result.is_err());
+    assert!(!created);
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_refuses_malformed_organization_id() {
+    let (result, created) = create_invitation_code_in("not-an-id", vec![], vec![]).await;
+
+    assert_eq!(ErrorKind::LogicError, result.unwrap_err().kind);
+    assert!(!created);
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_refuses_unknown_organization() {
+    let (result, created) =
+        create_invitation_code_in("653846b428c2649821284c60", vec![], vec![]).await;
+
+    assert!(result.is_err());
+    assert!(!created);
+}
+
+#[tokio::test]
+pub async fn create_invitation_code_refuses_role_of_another_organization() {
+    let (result, created) = create_invitation_code(vec![], vec![TEST_FOREIGN_ROLE_ID]).await;
+
+    assert!(result.is_err());
+    assert!(!created);
+}
+
+/// Redemption ids consumed and released by the storage stand-in.
+#[cfg(test)]
+#[derive(Default)]
+struct Redemptions {
+    consumed: Vec<String>,
+    released: Vec<String>,
+    joined: bool,
+}
+
+/// Answers the redemption of a single use code of the test organization, which is found only
+/// when `organization_exists`. 'gabriel' already is a member, so joining again is a duplicate.
+#[cfg(test)]
+fn spawn_redemption_storage(
+    organization_exists: bool,
+    redemptions: Arc<Mutex<Redemptions>>,
+) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::InvitationCode(Some(StorageInvitationCodeAction::Consume {
+            code,
+            redemption_id,
+            replier,
+            ..
+        })) => {
+            redemptions.lock().unwrap().consumed.push(redemption_id);
+
+            let invitation_code: InvitationCode = bson::from_document(bson::doc! {
+                "_id": bson::oid::ObjectId::new(),
+                "code": code,
+                "org_id": TEST_ORGANIZATION_ID,
+                "permissions": [],
+                "roles": [TEST_MEMBER_ROLE_ID],
+                "max_uses": 1i64,
+                "expires_at": null
+            })
+            .unwrap();
+
+            replier.send(Ok(Some(invitation_code))).unwrap();
+        }
+        StorageRequest::InvitationCode(Some(StorageInvitationCodeAction::Release {
+            redemption_id,
+            replier,
+            ..
+        })) => {
+            redemptions.lock().unwrap().released.push(redemption_id);
+            replier.send(Ok(())).unwrap();
+        }
+        StorageRequest::Organization(Some(OrganizationAction::Get { replier, .. })) => {
+            let organization = if organization_exists {
+                Some(create_test_organization("gabriel"))
+            } else {
+                None
+            };
+
+            replier.send(Ok(organization)).unwrap();
+        }
+        StorageRequest::Member(Some(MemberAction::Join {
+            user_id, replier, ..
+        })) => {
+            let write = match user_id.as_str() {
+                "gabriel" => UniqueWrite::Duplicate,
+                _ => {
+                    redemptions.lock().unwrap().joined = true;
+                    UniqueWrite::Written(())
+                }
+            };
+
+            replier.send(Ok(write)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn redeem_invitation_code(
+    organization_exists: bool,
+    user_id: &str,
+) -> (Result<String, Error>, Redemptions) {
+    let redemptions = Arc::new(Mutex::new(Redemptions::default()));
+    let sender = spawn_redemption_storage(organization_exists, redemptions.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_redeem_invitation_code(
+        &sender,
+        "code".to_string(),
+        user_id.to_string(),
+        api_replier,
+    )
+    .await;
+
+    let result = api_receiver.await.unwrap();
+    let redemptions = std::mem::take(&mut *redemptions.lock().unwrap());
+
+    (result, redemptions)
+}
+
+#[tokio::test]
+pub async fn redeem_invitation_code_joins_organization() {
+    let (result, redemptions) = redeem_invitation_code(true, "viewer").await;
+
+    assert_eq!(TEST_ORGANIZATION_ID, result.unwrap());
+    assert!(redemptions.joined);
+    assert!(redemptions.released.is_empty());
+}
+
+#[tokio::test]
+pub async fn redeem_invitation_code_releases_only_its_redemption_of_deleted_organization() {
+    let (result, redemptions) = redeem_invitation_code(false, "viewer").await;
+
+    assert!(result.is_err());
+    assert!(!redemptions.joined);
+    assert_eq!(1, redemptions.consumed.len());
+    assert_eq!(redemptions.consumed, redemptions.released);
+}
+
+#[tokio::test]
+pub async fn redeem_invitation_code_refuses_and_releases_redemption_of_existing_member() {
+    let (result, redemptions) = redeem_invitation_code(true, "gabriel").await;
+
+    assert!(result.is_err());
+    assert!(!redemptions.joined);
+    assert_eq!(1, redemptions.consumed.len());
+    assert_eq!(redemptions.consumed, redemptions.released);
+}
+
+#[test]
+pub fn omitted_max_uses_means_unlimited_uses() {
+    let (api_replier, _api_receiver) = tokio::sync::oneshot::channel();
+
+    let (_, expires_at, max_uses) =
+        validate_invitation_code_limits(api_replier, None, None).unwrap();
+
+    assert_eq!(None, expires_at);
+    assert_eq!(None, max_uses);
+}
+
+#[test]
+pub fn zero_max_uses_is_refused() {
+    let (api_replier, _api_receiver) = tokio::sync::oneshot::channel();
+
+    assert!(validate_invitation_code_limits(api_replier, None, Some(0)).is_err());
+}
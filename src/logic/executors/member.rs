@@ -0,0 +1,1348 @@
+use async_channel::Sender;
+use cp_microservice::{
+    core::error::{Error, ErrorKind},
+    logic::executor::{timeout_receive_storage_response, timeout_send_storage_request},
+};
+
+use crate::{
+    logic::{
+        actions::member_action::MemberAction,
+        authorization::{
+            authorize, authorize_grants, get_effective_permissions, PERMISSION_READ_MEMBERS,
+            PERMISSION_REMOVE_MEMBERS, PERMISSION_UPDATE_MEMBERS,
+            PERMISSION_UPDATE_MEMBER_PERMISSIONS,
+        },
+        executors::{
+            organization::get_organization,
+            role::{get_admin_role_id, list_organization_roles},
+        },
+        logic_request::LogicRequest,
+        member_roles::apply_role_changes,
+        role_hierarchy::collect_permissions,
+    },
+    storage::{
+        self,
+        member::Member,
+        member_page::{MemberPage, MemberSortField},
+        member_write::{MemberRolesChange, MemberWrite},
+        storage_request::StorageRequest,
+        user_organization::UserOrganization,
+    },
+};
+
+const TIMEOUT_LIST_USER_ORGANIZATIONS_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_GET_MEMBER_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_LIST_MEMBERS_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_UPDATE_MEMBER_ROLES_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_UPDATE_MEMBER_PERMISSIONS_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_DELETE_MEMBER_IN_MILLISECONDS: u64 = 10000u64;
+
+const DEFAULT_MEMBERS_PAGE_LIMIT: u32 = 50u32;
+const MAX_MEMBERS_PAGE_LIMIT: u32 = 200u32;
+
+pub async fn execute_member_action(
+    request: LogicRequest,
+    sender: Sender<StorageRequest>,
+    transactions_enabled: bool,
+) -> Result<(), Error> {
+    match request {
+        LogicRequest::Member(action) => match action {
+            Some(action) => match action {
+                MemberAction::ListUserOrganizations { user_id, replier } => {
+                    handle_list_user_organizations(&sender, user_id, replier).await
+                }
+                MemberAction::List {
+                    org_id,
+                    user_id,
+                    role_id,
+                    sort_by,
+                    descending,
+                    cursor,
+                    limit,
+                    replier,
+                } => {
+                    handle_list_members(
+                        &sender, org_id, user_id, role_id, sort_by, descending, cursor, limit,
+                        replier,
+                    )
+                    .await
+                }
+                MemberAction::UpdateRoles {
+                    org_id,
+                    user_id,
+                    member_user_id,
+                    roles,
+                    add,
+                    remove,
+                    replier,
+                } => {
+                    handle_update_member_roles(
+                        &sender,
+                        org_id,
+                        user_id,
+                        member_user_id,
+                        roles,
+                        add,
+                        remove,
+                        transactions_enabled,
+                        replier,
+                    )
+                    .await
+                }
+                MemberAction::UpdatePermissions {
+                    org_id,
+                    user_id,
+                    member_user_id,
+                    permissions,
+                    denied_permissions,
+                    replier,
+                } => {
+                    handle_update_member_permissions(
+                        &sender,
+                        org_id,
+                        user_id,
+                        member_user_id,
+                        permissions,
+                        denied_permissions,
+                        transactions_enabled,
+                        replier,
+                    )
+                    .await
+                }
+                MemberAction::Remove {
+                    org_id,
+                    user_id,
+                    member_user_id,
+                    replier,
+                } => {
+                    handle_remove_member(
+                        &sender,
+                        org_id,
+                        user_id,
+                        member_user_id,
+                        transactions_enabled,
+                        replier,
+                    )
+                    .await
+                }
+                MemberAction::Leave {
+                    org_id,
+                    user_id,
+                    replier,
+                } => {
+                    handle_leave_org(&sender, org_id, user_id, transactions_enabled, replier).await
+                }
+                MemberAction::CheckPermission {
+                    user_id,
+                    org_id,
+                    permission,
+                    replier,
+                } => handle_check_permission(&sender, user_id, org_id, permission, replier).await,
+            },
+            None => Err(Error::new(
+                ErrorKind::LogicError,
+                "[logic.member.execute_member_action] received 'None' as member action",
+            )),
+        },
+        _ => Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.execute_member_action] received an unexpected logic request",
+        )),
+    }
+}
+
+async fn handle_list_user_organizations(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<Vec<UserOrganization>, Error>>,
+) -> Result<(), Error> {
+    if user_id.is_empty() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.handle_list_user_organizations] user id is empty",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
+
+    let storage_request = StorageRequest::Member(Some(
+        storage::actions::member_action::MemberAction::ListUserOrganizations {
+            user_id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_LIST_USER_ORGANIZATIONS_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, user_organizations) = timeout_receive_storage_response(
+        TIMEOUT_LIST_USER_ORGANIZATIONS_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if let Err(_) = api_replier.send(Ok(user_organizations)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_list_members(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    role_id: Option<String>,
+    sort_by: Option<String>,
+    descending: Option<bool>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+    api_replier: tokio::sync::oneshot::Sender<Result<MemberPage, Error>>,
+) -> Result<(), Error> {
+    let sort_field = match sort_by {
+        Some(sort_by) => match MemberSortField::parse(&sort_by) {
+            Some(sort_field) => sort_field,
+            None => {
+                let error = Error::new(
+                    ErrorKind::LogicError,
+                    format!(
+                        "[logic.member.handle_list_members] cannot sort members by '{}', expected 'joined_at' or 'user_id'",
+                        sort_by
+                    ),
+                );
+
+                if let Err(_) = api_replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to api with an error");
+                }
+
+                return Err(error);
+            }
+        },
+        None => MemberSortField::JoinedAt,
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_MEMBERS_PAGE_LIMIT);
+
+    if limit == 0u32 || limit > MAX_MEMBERS_PAGE_LIMIT {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.member.handle_list_members] limit must be between 1 and {}",
+                MAX_MEMBERS_PAGE_LIMIT
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let mut api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_READ_MEMBERS,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<MemberPage, Error>>();
+
+    let storage_request = StorageRequest::Member(Some(
+        storage::actions::member_action::MemberAction::ListByOrganization {
+            organization_id: org_id,
+            role_id,
+            sort_field,
+            descending: descending.unwrap_or(false),
+            cursor,
+            limit,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_LIST_MEMBERS_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member_page) = timeout_receive_storage_response(
+        TIMEOUT_LIST_MEMBERS_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if let Err(_) = api_replier.send(Ok(member_page)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_update_member_roles(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    member_user_id: String,
+    roles: Option<Vec<String>>,
+    add: Vec<String>,
+    remove: Vec<String>,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let message = if member_user_id.is_empty() {
+        Some("member user id is empty")
+    } else if roles.is_some() && (!add.is_empty() || !remove.is_empty()) {
+        Some("roles can either be replaced or added and removed, not both")
+    } else if roles.is_none() && add.is_empty() && remove.is_empty() {
+        Some("no role changes were requested")
+    } else {
+        None
+    };
+
+    if let Some(message) = message {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!("[logic.member.handle_update_member_roles] {}", message),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_UPDATE_MEMBERS,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member) =
+        get_member(sender, member_user_id.clone(), org_id.clone(), api_replier).await?;
+
+    let member = match member {
+        Some(member) => member,
+        None => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.member.handle_update_member_roles] member not found",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let replaces_roles = roles.is_some();
+    let new_roles = apply_role_changes(member.roles(), roles, &add, &remove);
+
+    let (api_replier, available_roles) =
+        list_organization_roles(sender, org_id.clone(), api_replier).await?;
+
+    // Roles the member already holds are kept even if they are not available anymore, only the
+    // newly granted ones must exist within the organization.
+    if let Some(unknown_role) = new_roles.iter().find(|&role_id| {
+        !member.roles().contains(role_id)
+            && !available_roles.iter().any(|role| &role.id() == role_id)
+    }) {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.member.handle_update_member_roles] role '{}' does not exist",
+                unknown_role
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    // Every permission granted through the newly given roles, including the ones they inherit,
+    // must be held by the caller, otherwise updating members would allow granting oneself admin.
+    let granted_roles: Vec<String> = new_roles
+        .iter()
+        .filter(|&role_id| !member.roles().contains(role_id))
+        .cloned()
+        .collect();
+    let granted = collect_permissions(&available_roles, &granted_roles);
+
+    let api_replier = authorize_grants(sender, &user_id, &org_id, &granted, api_replier).await?;
+
+    let (mut api_replier, admin_role_id) = get_admin_role_id(sender, api_replier).await?;
+
+    // Replacing is only safe against the roles validated above, whereas added and removed roles
+    // are applied on top of whatever the member holds when written.
+    let change = if replaces_roles {
+        MemberRolesChange::Replace {
+            previous: member.roles().to_vec(),
+            roles: new_roles,
+        }
+    } else {
+        MemberRolesChange::Change {
+            add: apply_role_changes(&[], Some(add), &[], &remove),
+            remove,
+        }
+    };
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<MemberWrite, Error>>();
+
+    let storage_request = StorageRequest::Member(Some(
+        storage::actions::member_action::MemberAction::UpdateRoles {
+            user_id: member_user_id,
+            organization_id: org_id,
+            change,
+            admin_role_id,
+            within_transaction: transactions_enabled,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_UPDATE_MEMBER_ROLES_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member_write) = timeout_receive_storage_response(
+        TIMEOUT_UPDATE_MEMBER_ROLES_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    reply_member_write(member_write, "handle_update_member_roles", api_replier)
+}
+
+async fn handle_update_member_permissions(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    member_user_id: String,
+    permissions: Option<Vec<String>>,
+    denied_permissions: Option<Vec<String>>,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let has_empty_permission = permissions
+        .iter()
+        .chain(denied_permissions.iter())
+        .flatten()
+        .any(|permission| permission.is_empty());
+
+    let message = if member_user_id.is_empty() {
+        Some("member user id is empty")
+    } else if permissions.is_none() && denied_permissions.is_none() {
+        Some("no permission changes were requested")
+    } else if has_empty_permission {
+        Some("permissions must not be empty")
+    } else {
+        None
+    };
+
+    if let Some(message) = message {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!(
+                "[logic.member.handle_update_member_permissions] {}",
+                message
+            ),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_UPDATE_MEMBER_PERMISSIONS,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member) =
+        get_member(sender, member_user_id.clone(), org_id.clone(), api_replier).await?;
+
+    let member = match member {
+        Some(member) => member,
+        None => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.member.handle_update_member_permissions] member not found",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    // Permissions the member was already granted are kept as they are, only the newly granted
+    // ones must be held by the caller.
+    let granted: Vec<String> = permissions
+        .iter()
+        .flatten()
+        .filter(|&permission| !member.permissions().contains(permission))
+        .cloned()
+        .collect();
+
+    let api_replier = authorize_grants(sender, &user_id, &org_id, &granted, api_replier).await?;
+
+    let (api_replier, organization) = get_organization(sender, org_id.clone(), api_replier).await?;
+
+    let denies_owner = organization.is_some_and(|organization| {
+        organization.owner() == member_user_id
+            && denied_permissions
+                .as_ref()
+                .is_some_and(|denied_permissions| !denied_permissions.is_empty())
+    });
+
+    if denies_owner {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.handle_update_member_permissions] permissions cannot be denied to the owner",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (mut api_replier, admin_role_id) = get_admin_role_id(sender, api_replier).await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<MemberWrite, Error>>();
+
+    let storage_request = StorageRequest::Member(Some(
+        storage::actions::member_action::MemberAction::UpdatePermissions {
+            user_id: member_user_id,
+            organization_id: org_id,
+            permissions,
+            denied_permissions,
+            admin_role_id,
+            within_transaction: transactions_enabled,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_UPDATE_MEMBER_PERMISSIONS_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member_write) = timeout_receive_storage_response(
+        TIMEOUT_UPDATE_MEMBER_PERMISSIONS_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    reply_member_write(
+        member_write,
+        "handle_update_member_permissions",
+        api_replier,
+    )
+}
+
+async fn handle_remove_member(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    member_user_id: String,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    if member_user_id.is_empty() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.handle_remove_member] member user id is empty",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_REMOVE_MEMBERS,
+        api_replier,
+    )
+    .await?;
+
+    delete_member(
+        sender,
+        org_id,
+        member_user_id,
+        transactions_enabled,
+        api_replier,
+    )
+    .await
+}
+
+async fn handle_leave_org(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    if user_id.is_empty() || org_id.is_empty() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.handle_leave_org] user id and organization id must not be empty",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    delete_member(sender, org_id, user_id, transactions_enabled, api_replier).await
+}
+
+async fn delete_member(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let (api_replier, organization) = get_organization(sender, org_id.clone(), api_replier).await?;
+
+    if organization.is_some_and(|organization| organization.owner() == user_id) {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.delete_member] the owner must transfer the organization before leaving it",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (mut api_replier, admin_role_id) = get_admin_role_id(sender, api_replier).await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<MemberWrite, Error>>();
+
+    let storage_request = StorageRequest::Member(Some(
+        storage::actions::member_action::MemberAction::Delete {
+            user_id,
+            organization_id: org_id,
+            admin_role_id,
+            within_transaction: transactions_enabled,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_DELETE_MEMBER_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member_write) = timeout_receive_storage_response(
+        TIMEOUT_DELETE_MEMBER_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    reply_member_write(member_write, "delete_member", api_replier)
+}
+
+/// Replies to the api with the result of a member write guarded against removing the last
+/// admin.
+fn reply_member_write(
+    member_write: MemberWrite,
+    location: &str,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let message = match member_write {
+        MemberWrite::Applied => {
+            if let Err(_) = api_replier.send(Ok(())) {
+                log::warn!("failed to reply to api with an ok");
+            }
+
+            return Ok(());
+        }
+        MemberWrite::NotFound => "member not found or changed meanwhile",
+        MemberWrite::LastAdmin => "the organization must keep at least one admin",
+    };
+
+    let error = Error::new(
+        ErrorKind::LogicError,
+        format!("[logic.member.{}] {}", location, message),
+    );
+
+    if let Err(_) = api_replier.send(Err(error.clone())) {
+        log::warn!("failed to reply to api with an error");
+    }
+
+    Err(error)
+}
+
+async fn handle_check_permission(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    org_id: String,
+    permission: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<bool, Error>>,
+) -> Result<(), Error> {
+    if user_id.is_empty() || org_id.is_empty() || permission.is_empty() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.member.handle_check_permission] user id, organization id and permission must not be empty",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let (api_replier, permissions) =
+        get_effective_permissions(sender, user_id, org_id, api_replier).await?;
+
+    let allowed = match permissions {
+        Some(permissions) => permissions.allows(&permission),
+        None => false,
+    };
+
+    if let Err(_) = api_replier.send(Ok(allowed)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn get_member<T>(
+    sender: &Sender<StorageRequest>,
+    user_id: String,
+    organization_id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<T, Error>>,
+        Option<Member>,
+    ),
+    Error,
+> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<Option<Member>, Error>>();
+
+    let storage_request =
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Get {
+            user_id,
+            organization_id,
+            replier: storage_replier,
+        }));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_GET_MEMBER_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, member) = timeout_receive_storage_response(
+        TIMEOUT_GET_MEMBER_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, member))
+}
+
+#[cfg(test)]
+use crate::{
+    logic::test_storage::{
+        create_test_member, create_test_roles, spawn_storage, TEST_MEMBER_ROLE_ID,
+        TEST_ORGANIZATION_ID,
+    },
+    storage::actions::role_action::RoleAction,
+};
+
+#[cfg(test)]
+fn spawn_check_permission_storage() -> Sender<StorageRequest> {
+    spawn_storage(|storage_request| match storage_request {
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Get {
+            user_id,
+            replier,
+            ..
+        })) => {
+            let member = match user_id.as_str() {
+                "gabriel" => Some(create_test_member(
+                    "gabriel",
+                    vec![TEST_MEMBER_ROLE_ID],
+                    vec![],
+                    vec!["org:members:read"],
+                )),
+                _ => None,
+            };
+
+            replier.send(Ok(member)).unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+            replier.send(Ok(create_test_roles())).unwrap();
+        }
+        // Every other organization stands for a soft deleted one, which is not found.
+        StorageRequest::Organization(Some(OrganizationAction::Get { id, replier })) => {
+            let organization = if id == TEST_ORGANIZATION_ID {
+                Some(create_test_organization("gabriel"))
+            } else {
+                None
+            };
+
+            replier.send(Ok(organization)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn check_permission(user_id: &str, permission: &str) -> bool {
+    check_permission_in(TEST_ORGANIZATION_ID, user_id, permission).await
+}
+
+#[cfg(test)]
+async fn check_permission_in(org_id: &str, user_id: &str, permission: &str) -> bool {
+    let sender = spawn_check_permission_storage();
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    handle_check_permission(
+        &sender,
+        user_id.to_string(),
+        org_id.to_string(),
+        permission.to_string(),
+        api_replier,
+    )
+    .await
+    .unwrap();
+
+    api_receiver.await.unwrap().unwrap()
+}
+
+#[tokio::test]
+pub async fn check_permission_allows_permission_granted_by_role() {
+    assert!(check_permission("gabriel", "org:read").await);
+}
+
+#[tokio::test]
+pub async fn check_permission_refuses_denied_permission() {
+    assert!(!check_permission("gabriel", "org:members:read").await);
+}
+
+#[tokio::test]
+pub async fn check_permission_refuses_member_of_deleted_organization() {
+    assert!(!check_permission_in("653846b428c2649821284c60", "gabriel", "org:read").await);
+}
+
+#[tokio::test]
+pub async fn check_permission_refuses_non_member() {
+    assert!(!check_permission("stranger", "org:read").await);
+}
+
+#[cfg(test)]
+use crate::logic::test_storage::{create_test_organization, TEST_ADMIN_ROLE_ID};
+#[cfg(test)]
+use crate::storage::actions::organization_action::OrganizationAction;
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Answers a member removal with `member_write`, recording whether it was asked to run within a
+/// transaction.
+#[cfg(test)]
+fn spawn_delete_member_storage(
+    member_write: MemberWrite,
+    within_transaction: Arc<Mutex<Option<bool>>>,
+) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Organization(Some(OrganizationAction::Get { replier, .. })) => {
+            replier
+                .send(Ok(Some(create_test_organization("owner"))))
+                .unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::GetAdminRoleId { replier })) => {
+            replier.send(Ok(TEST_ADMIN_ROLE_ID.to_string())).unwrap();
+        }
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Delete {
+            admin_role_id,
+            within_transaction: requested_within_transaction,
+            replier,
+            ..
+        })) => {
+            assert_eq!(TEST_ADMIN_ROLE_ID, admin_role_id);
+            *within_transaction.lock().unwrap() = Some(requested_within_transaction);
+            replier.send(Ok(member_write)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn leave_org(
+    user_id: &str,
+    member_write: MemberWrite,
+    transactions_enabled: bool,
+) -> (Result<(), Error>, Option<bool>) {
+    let within_transaction = Arc::new(Mutex::new(None));
+    let sender = spawn_delete_member_storage(member_write, within_transaction.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_leave_org(
+        &sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        transactions_enabled,
+        api_replier,
+    )
+    .await;
+
+    let within_transaction = *within_transaction.lock().unwrap();
+
+    (api_receiver.await.unwrap(), within_transaction)
+}
+
+#[tokio::test]
+pub async fn leave_org_deletes_member_within_transaction_when_enabled() {
+    let (result, within_transaction) = leave_org("gabriel", MemberWrite::Applied, true).await;
+
+    assert!(result.is_ok());
+    assert_eq!(Some(true), within_transaction);
+}
+
+#[tokio::test]
+pub async fn leave_org_deletes_member_with_recheck_when_transactions_are_disabled() {
+    let (result, within_transaction) = leave_org("gabriel", MemberWrite::Applied, false).await;
+
+    assert!(result.is_ok());
+    assert_eq!(Some(false), within_transaction);
+}
+
+#[tokio::test]
+pub async fn leave_org_fails_when_storage_refuses_to_remove_last_admin() {
+    let (result, _) = leave_org("gabriel", MemberWrite::LastAdmin, false).await;
+
+    match result {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::LogicError, error.kind),
+    }
+}
+
+#[tokio::test]
+pub async fn leave_org_fails_when_member_is_not_found() {
+    let (result, _) = leave_org("gabriel", MemberWrite::NotFound, true).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+pub async fn leave_org_refuses_owner_without_deleting() {
+    let (result, within_transaction) = leave_org("owner", MemberWrite::Applied, true).await;
+
+    assert!(result.is_err());
+    assert_eq!(None, within_transaction);
+}
+
+/// Answers a roles update of 'member' with `member_write`, recording the change written.
+/// 'gabriel' is an admin whereas 'manager' may update members besides the member role.
+#[cfg(test)]
+fn spawn_update_member_roles_storage(
+    member_write: MemberWrite,
+    change: Arc<Mutex<Option<MemberRolesChange>>>,
+) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Get {
+            user_id,
+            replier,
+            ..
+        })) => {
+            let member = match user_id.as_str() {
+                "gabriel" => {
+                    create_test_member("gabriel", vec![TEST_ADMIN_ROLE_ID], vec![], vec![])
+                }
+                "manager" => create_test_member(
+                    "manager",
+                    vec![TEST_MEMBER_ROLE_ID],
+                    vec!["org:members:update"],
+                    vec![],
+                ),
+                _ => create_test_member(&user_id, vec![TEST_MEMBER_ROLE_ID], vec![], vec![]),
+            };
+
+            replier.send(Ok(Some(member))).unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+            replier.send(Ok(create_test_roles())).unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::GetAdminRoleId { replier })) => {
+            replier.send(Ok(TEST_ADMIN_ROLE_ID.to_string())).unwrap();
+        }
+        StorageRequest::Organization(Some(OrganizationAction::Get { replier, .. })) => {
+            replier
+                .send(Ok(Some(create_test_organization("gabriel"))))
+                .unwrap();
+        }
+        StorageRequest::Member(Some(
+            storage::actions::member_action::MemberAction::UpdateRoles {
+                change: requested_change,
+                replier,
+                ..
+            },
+        )) => {
+            *change.lock().unwrap() = Some(requested_change);
+            replier.send(Ok(member_write)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn update_member_roles(
+    roles: Option<Vec<&str>>,
+    add: Vec<&str>,
+    remove: Vec<&str>,
+    member_write: MemberWrite,
+) -> (Result<(), Error>, Option<MemberRolesChange>) {
+    update_member_roles_by("gabriel", roles, add, remove, member_write).await
+}
+
+#[cfg(test)]
+async fn update_member_roles_by(
+    user_id: &str,
+    roles: Option<Vec<&str>>,
+    add: Vec<&str>,
+    remove: Vec<&str>,
+    member_write: MemberWrite,
+) -> (Result<(), Error>, Option<MemberRolesChange>) {
+    let change = Arc::new(Mutex::new(None));
+    let sender = spawn_update_member_roles_storage(member_write, change.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let to_strings =
+        |roles: Vec<&str>| -> Vec<String> { roles.iter().map(|role| role.to_string()).collect() };
+
+    let _ = handle_update_member_roles(
+        &sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        "member".to_string(),
+        roles.map(to_strings),
+        to_strings(add),
+        to_strings(remove),
+        false,
+        api_replier,
+    )
+    .await;
+
+    let change = change.lock().unwrap().take();
+
+    (api_receiver.await.unwrap(), change)
+}
+
+#[tokio::test]
+pub async fn update_member_roles_replaces_roles_only_if_unchanged() {
+    let (result, change) = update_member_roles(
+        Some(vec![TEST_ADMIN_ROLE_ID]),
+        vec![],
+        vec![],
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_ok());
+
+    match change {
+        Some(MemberRolesChange::Replace { previous, roles }) => {
+            assert_eq!(vec![TEST_MEMBER_ROLE_ID.to_string()], previous);
+            assert_eq!(vec![TEST_ADMIN_ROLE_ID.to_string()], roles);
+        }
+        change => panic!("expected a roles replacement got '{:?}'", change),
+    }
+}
+
+#[tokio::test]
+pub async fn update_member_roles_adds_and_removes_on_top_of_stored_roles() {
+    let (result, change) = update_member_roles(
+        None,
+        vec![TEST_ADMIN_ROLE_ID, TEST_ADMIN_ROLE_ID, TEST_MEMBER_ROLE_ID],
+        vec![TEST_MEMBER_ROLE_ID],
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_ok());
+
+    match change {
+        Some(MemberRolesChange::Change { add, remove }) => {
+            assert_eq!(vec![TEST_ADMIN_ROLE_ID.to_string()], add);
+            assert_eq!(vec![TEST_MEMBER_ROLE_ID.to_string()], remove);
+        }
+        change => panic!("expected a roles change got '{:?}'", change),
+    }
+}
+
+#[tokio::test]
+pub async fn update_member_roles_fails_when_roles_changed_meanwhile() {
+    let (result, _) = update_member_roles(
+        Some(vec![TEST_ADMIN_ROLE_ID]),
+        vec![],
+        vec![],
+        MemberWrite::NotFound,
+    )
+    .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+pub async fn update_member_roles_fails_when_storage_refuses_to_remove_last_admin() {
+    let (result, _) = update_member_roles(
+        None,
+        vec![],
+        vec![TEST_MEMBER_ROLE_ID],
+        MemberWrite::LastAdmin,
+    )
+    .await;
+
+    match result {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::LogicError, error.kind),
+    }
+}
+
+#[tokio::test]
+pub async fn update_member_roles_refuses_granting_admin_role_without_its_permissions() {
+    let (result, change) = update_member_roles_by(
+        "manager",
+        None,
+        vec![TEST_ADMIN_ROLE_ID],
+        vec![],
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(change.is_none());
+}
+
+#[tokio::test]
+pub async fn update_member_roles_lets_manager_remove_roles_it_cannot_grant() {
+    let (result, change) = update_member_roles_by(
+        "manager",
+        None,
+        vec![],
+        vec![TEST_MEMBER_ROLE_ID],
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(change.is_some());
+}
+
+/// Answers a permissions update with `member_write`, recording whether it reached the storage.
+/// 'gabriel' is an admin, 'manager' may only update permissions besides the member role and
+/// 'owner' owns the organization.
+#[cfg(test)]
+fn spawn_update_member_permissions_storage(
+    member_write: MemberWrite,
+    written: Arc<Mutex<bool>>,
+) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Member(Some(storage::actions::member_action::MemberAction::Get {
+            user_id,
+            replier,
+            ..
+        })) => {
+            let member = match user_id.as_str() {
+                "gabriel" | "owner" => {
+                    create_test_member(&user_id, vec![TEST_ADMIN_ROLE_ID], vec![], vec![])
+                }
+                "manager" => create_test_member(
+                    "manager",
+                    vec![TEST_MEMBER_ROLE_ID],
+                    vec![PERMISSION_UPDATE_MEMBER_PERMISSIONS],
+                    vec![],
+                ),
+                _ => create_test_member(&user_id, vec![TEST_MEMBER_ROLE_ID], vec![], vec![]),
+            };
+
+            replier.send(Ok(Some(member))).unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+            replier.send(Ok(create_test_roles())).unwrap();
+        }
+        StorageRequest::Organization(Some(OrganizationAction::Get { replier, .. })) => {
+            replier
+                .send(Ok(Some(create_test_organization("owner"))))
+                .unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::GetAdminRoleId { replier })) => {
+            replier.send(Ok(TEST_ADMIN_ROLE_ID.to_string())).unwrap();
+        }
+        StorageRequest::Member(Some(
+            storage::actions::member_action::MemberAction::UpdatePermissions { replier, .. },
+        )) => {
+            *written.lock().unwrap() = true;
+            replier.send(Ok(member_write)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn update_member_permissions(
+    user_id: &str,
+    member_user_id: &str,
+    permissions: Option<Vec<&str>>,
+    denied_permissions: Option<Vec<&str>>,
+    member_write: MemberWrite,
+) -> (Result<(), Error>, bool) {
+    let written = Arc::new(Mutex::new(false));
+    let sender = spawn_update_member_permissions_storage(member_write, written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let to_strings = |permissions: Vec<&str>| -> Vec<String> {
+        permissions
+            .iter()
+            .map(|permission| permission.to_string())
+            .collect()
+    };
+
+    let _ = handle_update_member_permissions(
+        &sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        member_user_id.to_string(),
+        permissions.map(to_strings),
+        denied_permissions.map(to_strings),
+        false,
+        api_replier,
+    )
+    .await;
+
+    let written = *written.lock().unwrap();
+
+    (api_receiver.await.unwrap(), written)
+}
+
+#[tokio::test]
+pub async fn update_member_permissions_grants_permission_held_by_caller() {
+    let (result, written) = update_member_permissions(
+        "manager",
+        "member",
+        Some(vec!["org:read"]),
+        None,
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(written);
+}
+
+#[tokio::test]
+pub async fn update_member_permissions_refuses_grant_not_held_by_caller() {
+    let (result, written) = update_member_permissions(
+        "manager",
+        "member",
+        Some(vec!["org:delete"]),
+        None,
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn update_member_permissions_refuses_denials_to_owner() {
+    let (result, written) = update_member_permissions(
+        "gabriel",
+        "owner",
+        None,
+        Some(vec!["*"]),
+        MemberWrite::Applied,
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn update_member_permissions_fails_when_denials_strip_last_admin() {
+    let (result, _) = update_member_permissions(
+        "gabriel",
+        "gabriel",
+        None,
+        Some(vec!["*"]),
+        MemberWrite::LastAdmin,
+    )
+    .await;
+
+    match result {
+        Ok(_) => panic!("expected 'Err' got 'Ok'"),
+        Err(error) => assert_eq!(ErrorKind::LogicError, error.kind),
+    }
+}
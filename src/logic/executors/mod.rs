@@ -1,2 +1,4 @@
 pub mod invitation_code;
+pub mod member;
 pub mod organization;
+pub mod role;
@@ -1,4 +1,5 @@
 use async_channel::Sender;
+use bson::oid::ObjectId;
 use celes::Country;
 use cp_core::geolocalization::address::Address;
 use cp_microservice::{
@@ -9,18 +10,34 @@ use cp_microservice::{
 use std::str::FromStr;
 
 use crate::{
-    logic::{actions::organization_action::OrganizationAction, logic_request::LogicRequest},
+    logic::{
+        actions::organization_action::OrganizationAction,
+        authorization::{
+            authorize, authorize_deleted, PERMISSION_DELETE_ORGANIZATION,
+            PERMISSION_READ_ORGANIZATION, PERMISSION_RESTORE_ORGANIZATION,
+            PERMISSION_TRANSFER_OWNERSHIP, PERMISSION_UPDATE_ORGANIZATION,
+        },
+        executors::{member::get_member, role::get_admin_role_id},
+        logic_request::LogicRequest,
+        saga::Saga,
+    },
     storage::{
-        actions::{member_action::MemberAction, role_action::RoleAction},
+        actions::member_action::MemberAction, organization::Organization,
         storage_request::StorageRequest,
     },
 };
 
 const TIMEOUT_CREATE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_GET_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_UPDATE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_DELETE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_RESTORE_ORGANIZATION_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_TRANSFER_OWNERSHIP_IN_MILLISECONDS: u64 = 10000u64;
 
-pub async fn create_organization(
+pub async fn execute_organization_action(
     request: LogicRequest,
     sender: Sender<StorageRequest>,
+    transactions_enabled: bool,
 ) -> Result<(), Error> {
     match request {
         LogicRequest::Organization(action) => match action {
@@ -32,22 +49,72 @@ pub async fn create_organization(
                     user_id,
                     replier,
                 } => {
-                    handle_create_organization(sender, country, name, address, user_id, replier)
-                        .await
+                    handle_create_organization(
+                        sender,
+                        country,
+                        name,
+                        address,
+                        user_id,
+                        transactions_enabled,
+                        replier,
+                    )
+                    .await
+                }
+                OrganizationAction::Get {
+                    id,
+                    user_id,
+                    replier,
+                } => handle_get_organization(sender, id, user_id, replier).await,
+                OrganizationAction::Update {
+                    id,
+                    country,
+                    name,
+                    address,
+                    user_id,
+                    replier,
+                } => {
+                    handle_update_organization(
+                        sender, id, country, name, address, user_id, replier,
+                    )
+                    .await
+                }
+                OrganizationAction::Delete {
+                    id,
+                    user_id,
+                    replier,
+                } => handle_delete_organization(sender, id, user_id, replier).await,
+                OrganizationAction::Restore {
+                    id,
+                    user_id,
+                    replier,
+                } => handle_restore_organization(sender, id, user_id, replier).await,
+                OrganizationAction::TransferOwnership {
+                    id,
+                    user_id,
+                    new_owner_id,
+                    demote_previous_owner,
+                    replier,
+                } => {
+                    handle_transfer_ownership(
+                        sender,
+                        id,
+                        user_id,
+                        new_owner_id,
+                        demote_previous_owner,
+                        transactions_enabled,
+                        replier,
+                    )
+                    .await
                 }
-                _ => Err(Error::new(
-                    ErrorKind::LogicError,
-                    "[logic.organization.create_organization] received an unexpected organization action",
-                )),
             },
             None => Err(Error::new(
                 ErrorKind::LogicError,
-                "[logic.organization.create_organization] received 'None' as organization action",
+                "[logic.organization.execute_organization_action] received 'None' as organization action",
             )),
         },
         _ => Err(Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.create_organization] received an unexpected logic request",
+            "[logic.organization.execute_organization_action] received an unexpected logic request",
         )),
     }
 }
@@ -58,41 +125,163 @@ async fn handle_create_organization(
     name: String,
     address: Address,
     user_id: String,
+    transactions_enabled: bool,
     api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
 ) -> Result<(), Error> {
     let api_replier = validate_create_organization_input(api_replier, &country, &name, &address)?;
 
     let (api_replier, admin_role_id) = get_admin_role_id(&sender, api_replier).await?;
 
-    let (api_replier, organization_id) =
-        create_organization_and_return_id(&sender, country, name, address, api_replier).await?;
+    let (api_replier, organization_id) = if transactions_enabled {
+        create_organization_with_admin_and_return_id(
+            &sender,
+            country,
+            name,
+            address,
+            user_id,
+            admin_role_id,
+            api_replier,
+        )
+        .await?
+    } else {
+        let mut saga = Saga::new(&sender);
+
+        let organization_id = ObjectId::new().to_hex();
+
+        let compensated_organization_id = organization_id.clone();
+        saga.register_compensation(move |replier| {
+            StorageRequest::Organization(Some(
+                crate::storage::actions::organization_action::OrganizationAction::Remove {
+                    id: compensated_organization_id,
+                    replier,
+                },
+            ))
+        });
+
+        let api_replier = saga
+            .step(create_organization(
+                &sender,
+                organization_id.clone(),
+                country,
+                name,
+                address,
+                user_id.clone(),
+                api_replier,
+            ))
+            .await?;
+
+        let api_replier = saga
+            .step(create_member(
+                &sender,
+                user_id,
+                admin_role_id,
+                organization_id.clone(),
+                api_replier,
+            ))
+            .await?;
+
+        (api_replier, organization_id)
+    };
+
+    if let Err(_) = api_replier.send(Ok(organization_id)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
 
-    let api_replier = create_member(
+async fn handle_get_organization(
+    sender: Sender<StorageRequest>,
+    id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
+) -> Result<(), Error> {
+    let api_replier = authorize(
         &sender,
-        user_id,
-        admin_role_id.clone(),
-        organization_id.clone(),
+        &user_id,
+        &id,
+        PERMISSION_READ_ORGANIZATION,
         api_replier,
     )
     .await?;
 
-    if let Err(_) = api_replier.send(Ok(organization_id)) {
+    let (api_replier, organization) = get_organization(&sender, id, api_replier).await?;
+
+    let organization = match organization {
+        Some(organization) => organization,
+        None => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.organization.handle_get_organization] organization not found",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = api_replier.send(Ok(organization)) {
         log::warn!("failed to reply to api with an ok");
     }
 
     Ok(())
 }
 
-fn validate_create_organization_input(
-    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
-    country: &String,
-    name: &String,
-    address: &Address,
-) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
-    if country.is_empty() {
+pub async fn get_organization<T>(
+    sender: &Sender<StorageRequest>,
+    id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<
+    (
+        tokio::sync::oneshot::Sender<Result<T, Error>>,
+        Option<Organization>,
+    ),
+    Error,
+> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<Option<Organization>, Error>>();
+
+    let storage_request = StorageRequest::Organization(Some(
+        crate::storage::actions::organization_action::OrganizationAction::Get {
+            id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_GET_ORGANIZATION_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, organization) = timeout_receive_storage_response(
+        TIMEOUT_GET_ORGANIZATION_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, organization))
+}
+
+async fn handle_update_organization(
+    sender: Sender<StorageRequest>,
+    id: String,
+    country: Option<String>,
+    name: Option<String>,
+    address: Option<Address>,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    if country.is_none() && name.is_none() && address.is_none() {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] country is empty",
+            "[logic.organization.handle_update_organization] no fields to update were provided",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -102,10 +291,54 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if name.is_empty() {
+    let api_replier = validate_organization_input(
+        api_replier,
+        country.as_ref(),
+        name.as_ref(),
+        address.as_ref(),
+    )?;
+
+    let mut api_replier = authorize(
+        &sender,
+        &user_id,
+        &id,
+        PERMISSION_UPDATE_ORGANIZATION,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<bool, Error>>();
+
+    let storage_request = StorageRequest::Organization(Some(
+        crate::storage::actions::organization_action::OrganizationAction::Update {
+            id,
+            country,
+            name,
+            address,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_UPDATE_ORGANIZATION_IN_MILLISECONDS,
+        storage_request,
+        &sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, found) = timeout_receive_storage_response(
+        TIMEOUT_UPDATE_ORGANIZATION_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if !found {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] name is empty",
+            "[logic.organization.handle_update_organization] organization not found",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -115,10 +348,57 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if address.country().is_empty() {
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_delete_organization(
+    sender: Sender<StorageRequest>,
+    id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let mut api_replier = authorize(
+        &sender,
+        &user_id,
+        &id,
+        PERMISSION_DELETE_ORGANIZATION,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<bool, Error>>();
+
+    let storage_request = StorageRequest::Organization(Some(
+        crate::storage::actions::organization_action::OrganizationAction::Delete {
+            id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_DELETE_ORGANIZATION_IN_MILLISECONDS,
+        storage_request,
+        &sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, found) = timeout_receive_storage_response(
+        TIMEOUT_DELETE_ORGANIZATION_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if !found {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] address country is empty",
+            "[logic.organization.handle_delete_organization] organization not found",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -128,10 +408,57 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if address.city().is_empty() {
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_restore_organization(
+    sender: Sender<StorageRequest>,
+    id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let mut api_replier = authorize_deleted(
+        &sender,
+        &user_id,
+        &id,
+        PERMISSION_RESTORE_ORGANIZATION,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<bool, Error>>();
+
+    let storage_request = StorageRequest::Organization(Some(
+        crate::storage::actions::organization_action::OrganizationAction::Restore {
+            id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_RESTORE_ORGANIZATION_IN_MILLISECONDS,
+        storage_request,
+        &sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, found) = timeout_receive_storage_response(
+        TIMEOUT_RESTORE_ORGANIZATION_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    if !found {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] address city is empty",
+            "[logic.organization.handle_restore_organization] no deleted organization found to restore",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -141,10 +468,26 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if address.street().is_empty() {
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_transfer_ownership(
+    sender: Sender<StorageRequest>,
+    id: String,
+    user_id: String,
+    new_owner_id: String,
+    demote_previous_owner: bool,
+    transactions_enabled: bool,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    if new_owner_id.is_empty() {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] address street is empty",
+            "[logic.organization.handle_transfer_ownership] new owner id is empty",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -154,10 +497,29 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if address.number().is_empty() {
+    let (api_replier, organization) = get_organization(&sender, id.clone(), api_replier).await?;
+
+    let previous_owner = match organization {
+        Some(organization) => organization.owner().to_string(),
+        None => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.organization.handle_transfer_ownership] organization not found",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    // Only the owner may give up its own admin role, others cannot demote it on its behalf.
+    if demote_previous_owner && previous_owner != user_id {
         let error = Error::new(
             ErrorKind::LogicError,
-            "[logic.organization.validate_input] address number is empty",
+            "[logic.organization.handle_transfer_ownership] only the owner may demote itself",
         );
 
         if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -167,28 +529,56 @@ fn validate_create_organization_input(
         return Err(error);
     }
 
-    if address.postal_code().is_empty() {
-        let error = Error::new(
-            ErrorKind::LogicError,
-            "[logic.organization.validate_input] address postal code is empty",
-        );
+    // Besides the owner, members granted the permission may transfer the organization too, but
+    // only once its owner is gone so it can still be handed over.
+    let api_replier = if previous_owner == user_id {
+        api_replier
+    } else {
+        let api_replier = authorize(
+            &sender,
+            &user_id,
+            &id,
+            PERMISSION_TRANSFER_OWNERSHIP,
+            api_replier,
+        )
+        .await?;
 
-        if let Err(_) = api_replier.send(Err(error.clone())) {
-            log::warn!("failed to reply to api with an error");
+        let (api_replier, owner_member) = if previous_owner.is_empty() {
+            (api_replier, None)
+        } else {
+            get_member(&sender, previous_owner.clone(), id.clone(), api_replier).await?
+        };
+
+        if owner_member.is_some() {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.organization.handle_transfer_ownership] only the owner may transfer the organization while it is a member",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
         }
 
-        return Err(error);
-    }
+        api_replier
+    };
+
+    let (api_replier, new_owner) =
+        get_member(&sender, new_owner_id.clone(), id.clone(), api_replier).await?;
+
+    let new_owner = match new_owner {
+        Some(new_owner) if new_owner_id != previous_owner => new_owner,
+        new_owner => {
+            let message = match new_owner {
+                Some(_) => "user already owns the organization",
+                None => "new owner is not a member of the organization",
+            };
 
-    match Country::from_str(country.as_str()) {
-        Ok(_) => (),
-        Err(error) => {
             let error = Error::new(
                 ErrorKind::LogicError,
-                format!(
-                    "[logic.organization.validate_input] invalid country specified: {}",
-                    &error
-                ),
+                format!("[logic.organization.handle_transfer_ownership] {}", message),
             );
 
             if let Err(_) = api_replier.send(Err(error.clone())) {
@@ -199,19 +589,292 @@ fn validate_create_organization_input(
         }
     };
 
+    let (api_replier, admin_role_id) = get_admin_role_id(&sender, api_replier).await?;
+
+    let api_replier = if transactions_enabled {
+        run_ownership_step(
+            &sender,
+            |replier| {
+                StorageRequest::Organization(Some(
+                    crate::storage::actions::organization_action::OrganizationAction::TransferOwnership {
+                        id,
+                        previous_owner,
+                        new_owner: new_owner_id,
+                        admin_role_id,
+                        demote_previous_owner,
+                        replier,
+                    },
+                ))
+            },
+            api_replier,
+        )
+        .await?
+    } else {
+        let (api_replier, previous_owner_member) =
+            get_member(&sender, previous_owner.clone(), id.clone(), api_replier).await?;
+
+        let mut saga = Saga::new(&sender);
+
+        let (compensated_id, compensated_previous_owner, compensated_new_owner) =
+            (id.clone(), previous_owner.clone(), new_owner_id.clone());
+        saga.register_compensation(move |replier| {
+            StorageRequest::Organization(Some(
+                crate::storage::actions::organization_action::OrganizationAction::SetOwner {
+                    id: compensated_id,
+                    previous_owner: compensated_new_owner,
+                    owner: compensated_previous_owner,
+                    replier,
+                },
+            ))
+        });
+
+        let (step_id, step_previous_owner, step_new_owner) =
+            (id.clone(), previous_owner.clone(), new_owner_id.clone());
+        let api_replier = saga
+            .step(run_ownership_step(
+                &sender,
+                move |replier| {
+                    StorageRequest::Organization(Some(
+                        crate::storage::actions::organization_action::OrganizationAction::SetOwner {
+                            id: step_id,
+                            previous_owner: step_previous_owner,
+                            owner: step_new_owner,
+                            replier,
+                        },
+                    ))
+                },
+                api_replier,
+            ))
+            .await?;
+
+        let api_replier = if new_owner.roles().contains(&admin_role_id) {
+            api_replier
+        } else {
+            let (compensated_id, compensated_new_owner, compensated_admin_role_id) =
+                (id.clone(), new_owner_id.clone(), admin_role_id.clone());
+            saga.register_compensation(move |replier| {
+                StorageRequest::Member(Some(MemberAction::RemoveRole {
+                    user_id: compensated_new_owner,
+                    organization_id: compensated_id,
+                    role_id: compensated_admin_role_id,
+                    replier,
+                }))
+            });
+
+            let (step_id, step_new_owner, step_admin_role_id) =
+                (id.clone(), new_owner_id.clone(), admin_role_id.clone());
+            let api_replier = saga
+                .step(run_ownership_step(
+                    &sender,
+                    move |replier| {
+                        StorageRequest::Member(Some(MemberAction::AddRole {
+                            user_id: step_new_owner,
+                            organization_id: step_id,
+                            role_id: step_admin_role_id,
+                            replier,
+                        }))
+                    },
+                    api_replier,
+                ))
+                .await?;
+
+            api_replier
+        };
+
+        let previous_owner_is_admin =
+            previous_owner_member.is_some_and(|member| member.roles().contains(&admin_role_id));
+
+        if demote_previous_owner && previous_owner_is_admin {
+            let (compensated_id, compensated_previous_owner, compensated_admin_role_id) =
+                (id.clone(), previous_owner.clone(), admin_role_id.clone());
+            saga.register_compensation(move |replier| {
+                StorageRequest::Member(Some(MemberAction::AddRole {
+                    user_id: compensated_previous_owner,
+                    organization_id: compensated_id,
+                    role_id: compensated_admin_role_id,
+                    replier,
+                }))
+            });
+
+            saga.step(run_ownership_step(
+                &sender,
+                move |replier| {
+                    StorageRequest::Member(Some(MemberAction::RemoveRole {
+                        user_id: previous_owner,
+                        organization_id: id,
+                        role_id: admin_role_id,
+                        replier,
+                    }))
+                },
+                api_replier,
+            ))
+            .await?
+        } else {
+            api_replier
+        }
+    };
+
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn run_ownership_step(
+    sender: &Sender<StorageRequest>,
+    storage_request: impl FnOnce(tokio::sync::oneshot::Sender<Result<(), Error>>) -> StorageRequest,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<(), Error>>, Error> {
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_TRANSFER_OWNERSHIP_IN_MILLISECONDS,
+        storage_request(storage_replier),
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, _) = timeout_receive_storage_response(
+        TIMEOUT_TRANSFER_OWNERSHIP_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
     Ok(api_replier)
 }
 
-async fn get_admin_role_id(
+fn validate_create_organization_input(
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+    country: &String,
+    name: &String,
+    address: &Address,
+) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
+    validate_organization_input(api_replier, Some(country), Some(name), Some(address))
+}
+
+fn validate_organization_input<T>(
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+    country: Option<&String>,
+    name: Option<&String>,
+    address: Option<&Address>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    if let Err(error) = check_organization_input(country, name, address) {
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    Ok(api_replier)
+}
+
+fn check_organization_input(
+    country: Option<&String>,
+    name: Option<&String>,
+    address: Option<&Address>,
+) -> Result<(), Error> {
+    if let Some(country) = country {
+        if country.is_empty() {
+            return Err(Error::new(
+                ErrorKind::LogicError,
+                "[logic.organization.validate_input] country is empty",
+            ));
+        }
+    }
+
+    if let Some(name) = name {
+        if name.is_empty() {
+            return Err(Error::new(
+                ErrorKind::LogicError,
+                "[logic.organization.validate_input] name is empty",
+            ));
+        }
+    }
+
+    if let Some(address) = address {
+        check_address(address)?;
+    }
+
+    if let Some(country) = country {
+        if let Err(error) = Country::from_str(country.as_str()) {
+            return Err(Error::new(
+                ErrorKind::LogicError,
+                format!(
+                    "[logic.organization.validate_input] invalid country specified: {}",
+                    &error
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_address(address: &Address) -> Result<(), Error> {
+    if address.country().is_empty() {
+        return Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.organization.validate_input] address country is empty",
+        ));
+    }
+
+    if address.city().is_empty() {
+        return Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.organization.validate_input] address city is empty",
+        ));
+    }
+
+    if address.street().is_empty() {
+        return Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.organization.validate_input] address street is empty",
+        ));
+    }
+
+    if address.number().is_empty() {
+        return Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.organization.validate_input] address number is empty",
+        ));
+    }
+
+    if address.postal_code().is_empty() {
+        return Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.organization.validate_input] address postal code is empty",
+        ));
+    }
+
+    Ok(())
+}
+
+async fn create_organization_with_admin_and_return_id(
     sender: &Sender<StorageRequest>,
+    country: String,
+    name: String,
+    address: Address,
+    user_id: String,
+    admin_role_id: String,
     mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
 ) -> Result<(tokio::sync::oneshot::Sender<Result<String, Error>>, String), Error> {
     let (storage_replier, storage_receiver) =
         tokio::sync::oneshot::channel::<Result<String, Error>>();
 
-    let storage_request = StorageRequest::Role(Some(RoleAction::GetAdminRoleId {
-        replier: storage_replier,
-    }));
+    let storage_request = StorageRequest::Organization(Some(
+        crate::storage::actions::organization_action::OrganizationAction::CreateWithAdmin {
+            country,
+            name,
+            address,
+            user_id,
+            admin_role_id,
+            replier: storage_replier,
+        },
+    ));
 
     api_replier = timeout_send_storage_request(
         TIMEOUT_CREATE_ORGANIZATION_IN_MILLISECONDS,
@@ -221,31 +884,35 @@ async fn get_admin_role_id(
     )
     .await?;
 
-    let (api_replier, admin_role_id) = timeout_receive_storage_response(
+    let (api_replier, _) = timeout_receive_storage_response(
         TIMEOUT_CREATE_ORGANIZATION_IN_MILLISECONDS,
         storage_receiver,
         api_replier,
     )
     .await?;
 
-    Ok((api_replier, admin_role_id))
+    Ok(api_replier)
 }
 
-async fn create_organization_and_return_id(
+async fn create_organization(
     sender: &Sender<StorageRequest>,
+    id: String,
     country: String,
     name: String,
     address: Address,
+    owner: String,
     mut api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
-) -> Result<(tokio::sync::oneshot::Sender<Result<String, Error>>, String), Error> {
+) -> Result<tokio::sync::oneshot::Sender<Result<String, Error>>, Error> {
     let (storage_replier, storage_receiver) =
         tokio::sync::oneshot::channel::<Result<String, Error>>();
 
     let storage_request = StorageRequest::Organization(Some(
         crate::storage::actions::organization_action::OrganizationAction::Create {
+            id,
             country,
             name,
             address,
+            owner,
             replier: storage_replier,
         },
     ));
@@ -301,3 +968,342 @@ async fn create_member(
 
     Ok(api_replier)
 }
+
+#[cfg(test)]
+use crate::logic::test_storage::{
+    create_test_member, create_test_roles, spawn_storage, TEST_ADMIN_ROLE_ID, TEST_MEMBER_ROLE_ID,
+    TEST_ORGANIZATION_ID,
+};
+#[cfg(test)]
+use crate::storage::actions::{
+    organization_action::OrganizationAction as StorageOrganizationAction, role_action::RoleAction,
+};
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Answers deletions and restorations with `found`, recording whether any reached the storage.
+/// 'gabriel' is an admin of the organization whereas 'viewer' only holds the member role.
+#[cfg(test)]
+fn spawn_soft_deletion_storage(found: bool, written: Arc<AtomicBool>) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Member(Some(MemberAction::Get {
+            user_id, replier, ..
+        })) => {
+            let roles = match user_id.as_str() {
+                "gabriel" => vec![TEST_ADMIN_ROLE_ID],
+                _ => vec![TEST_MEMBER_ROLE_ID],
+            };
+
+            replier
+                .send(Ok(Some(create_test_member(
+                    &user_id,
+                    roles,
+                    vec![],
+                    vec![],
+                ))))
+                .unwrap();
+        }
+        StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+            replier.send(Ok(create_test_roles())).unwrap();
+        }
+        StorageRequest::Organization(Some(StorageOrganizationAction::Get { replier, .. })) => {
+            replier
+                .send(Ok(Some(create_test_organization("gabriel"))))
+                .unwrap();
+        }
+        StorageRequest::Organization(Some(StorageOrganizationAction::Delete {
+            replier, ..
+        }))
+        | StorageRequest::Organization(Some(StorageOrganizationAction::Restore {
+            replier, ..
+        })) => {
+            written.store(true, Ordering::SeqCst);
+            replier.send(Ok(found)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn delete_organization(user_id: &str, found: bool) -> (Result<(), Error>, bool) {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_soft_deletion_storage(found, written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_delete_organization(
+        sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        api_replier,
+    )
+    .await;
+
+    (api_receiver.await.unwrap(), written.load(Ordering::SeqCst))
+}
+
+#[cfg(test)]
+async fn restore_organization(user_id: &str, found: bool) -> (Result<(), Error>, bool) {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_soft_deletion_storage(found, written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_restore_organization(
+        sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        api_replier,
+    )
+    .await;
+
+    (api_receiver.await.unwrap(), written.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+pub async fn delete_org_soft_deletes_organization_for_admin() {
+    let (result, written) = delete_organization("gabriel", true).await;
+
+    assert!(result.is_ok());
+    assert!(written);
+}
+
+#[tokio::test]
+pub async fn delete_org_refuses_member_without_permission() {
+    let (result, written) = delete_organization("viewer", true).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn delete_org_fails_when_organization_is_not_found() {
+    let (result, _) = delete_organization("gabriel", false).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+pub async fn restore_org_restores_organization_for_admin() {
+    let (result, written) = restore_organization("gabriel", true).await;
+
+    assert!(result.is_ok());
+    assert!(written);
+}
+
+#[tokio::test]
+pub async fn restore_org_refuses_member_without_permission() {
+    let (result, written) = restore_organization("viewer", true).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn restore_org_fails_when_organization_is_not_deleted() {
+    let (result, _) = restore_organization("gabriel", false).await;
+
+    assert!(result.is_err());
+}
+
+#[cfg(test)]
+use crate::logic::test_storage::create_test_organization;
+#[cfg(test)]
+use std::sync::Mutex;
+
+/// Answers an ownership transfer of an organization owned by `owner`, recording every write
+/// as text and failing the ones listed in `failing`. 'owner' and 'gabriel' are admins whereas
+/// 'newbie' only holds the member role, and 'former' is no longer a member.
+#[cfg(test)]
+fn spawn_ownership_storage(
+    owner: &'static str,
+    failing: Vec<&'static str>,
+    writes: Arc<Mutex<Vec<String>>>,
+) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| {
+        let (write, replier) = match storage_request {
+            StorageRequest::Organization(Some(StorageOrganizationAction::Get {
+                replier, ..
+            })) => {
+                replier
+                    .send(Ok(Some(create_test_organization(owner))))
+                    .unwrap();
+                return;
+            }
+            StorageRequest::Member(Some(MemberAction::Get {
+                user_id, replier, ..
+            })) => {
+                let roles = match user_id.as_str() {
+                    "former" => {
+                        replier.send(Ok(None)).unwrap();
+                        return;
+                    }
+                    "owner" | "gabriel" => vec![TEST_ADMIN_ROLE_ID],
+                    _ => vec![TEST_MEMBER_ROLE_ID],
+                };
+
+                replier
+                    .send(Ok(Some(create_test_member(
+                        &user_id,
+                        roles,
+                        vec![],
+                        vec![],
+                    ))))
+                    .unwrap();
+                return;
+            }
+            StorageRequest::Role(Some(RoleAction::ListByOrganization { replier, .. })) => {
+                replier.send(Ok(create_test_roles())).unwrap();
+                return;
+            }
+            StorageRequest::Role(Some(RoleAction::GetAdminRoleId { replier })) => {
+                replier.send(Ok(TEST_ADMIN_ROLE_ID.to_string())).unwrap();
+                return;
+            }
+            StorageRequest::Organization(Some(StorageOrganizationAction::TransferOwnership {
+                previous_owner,
+                new_owner,
+                demote_previous_owner,
+                replier,
+                ..
+            })) => (
+                format!(
+                    "transfer {} -> {} demoting {}",
+                    previous_owner, new_owner, demote_previous_owner
+                ),
+                replier,
+            ),
+            StorageRequest::Organization(Some(StorageOrganizationAction::SetOwner {
+                previous_owner,
+                owner,
+                replier,
+                ..
+            })) => (
+                format!("set owner {} -> {}", previous_owner, owner),
+                replier,
+            ),
+            StorageRequest::Member(Some(MemberAction::AddRole {
+                user_id, replier, ..
+            })) => (format!("add admin {}", user_id), replier),
+            StorageRequest::Member(Some(MemberAction::RemoveRole {
+                user_id, replier, ..
+            })) => (format!("remove admin {}", user_id), replier),
+            _ => panic!("unexpected storage request"),
+        };
+
+        let result = if failing.iter().any(|failing| write.starts_with(failing)) {
+            Err(Error::new(ErrorKind::StorageError, "failing write"))
+        } else {
+            Ok(())
+        };
+
+        writes.lock().unwrap().push(write);
+        replier.send(result).unwrap();
+    })
+}
+
+#[cfg(test)]
+async fn transfer_ownership(
+    owner: &'static str,
+    user_id: &str,
+    demote_previous_owner: bool,
+    transactions_enabled: bool,
+    failing: Vec<&'static str>,
+) -> (Result<(), Error>, Vec<String>) {
+    let writes = Arc::new(Mutex::new(Vec::new()));
+    let sender = spawn_ownership_storage(owner, failing, writes.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_transfer_ownership(
+        sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        user_id.to_string(),
+        "newbie".to_string(),
+        demote_previous_owner,
+        transactions_enabled,
+        api_replier,
+    )
+    .await;
+
+    let writes = writes.lock().unwrap().clone();
+
+    (api_receiver.await.unwrap(), writes)
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_runs_single_transaction_when_enabled() {
+    let (result, writes) = transfer_ownership("owner", "owner", true, true, vec![]).await;
+
+    assert!(result.is_ok());
+    assert_eq!(vec!["transfer owner -> newbie demoting true"], writes);
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_runs_saga_steps_in_order() {
+    let (result, writes) = transfer_ownership("owner", "owner", true, false, vec![]).await;
+
+    assert!(result.is_ok());
+    assert_eq!(
+        vec![
+            "set owner owner -> newbie",
+            "add admin newbie",
+            "remove admin owner"
+        ],
+        writes
+    );
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_compensates_saga_steps_including_failed_one_in_reverse_order() {
+    let (result, writes) =
+        transfer_ownership("owner", "owner", true, false, vec!["remove admin owner"]).await;
+
+    assert!(result.is_err());
+    assert_eq!(
+        vec![
+            "set owner owner -> newbie",
+            "add admin newbie",
+            "remove admin owner",
+            "add admin owner",
+            "remove admin newbie",
+            "set owner newbie -> owner"
+        ],
+        writes
+    );
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_refuses_demoting_owner_on_its_behalf() {
+    let (result, writes) = transfer_ownership("owner", "gabriel", true, false, vec![]).await;
+
+    assert!(result.is_err());
+    assert!(writes.is_empty());
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_hands_over_organization_without_owner() {
+    let (result, writes) = transfer_ownership("", "gabriel", false, false, vec![]).await;
+
+    assert!(result.is_ok());
+    assert_eq!(vec!["set owner  -> newbie", "add admin newbie"], writes);
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_refuses_taking_organization_from_present_owner() {
+    let (result, writes) = transfer_ownership("owner", "gabriel", false, false, vec![]).await;
+
+    assert!(result.is_err());
+    assert!(writes.is_empty());
+}
+
+#[tokio::test]
+pub async fn transfer_ownership_hands_over_organization_of_former_member() {
+    let (result, writes) = transfer_ownership("former", "gabriel", false, false, vec![]).await;
+
+    assert!(result.is_ok());
+    assert_eq!(
+        vec!["set owner former -> newbie", "add admin newbie"],
+        writes
+    );
+}
@@ -0,0 +1,843 @@
+use async_channel::Sender;
+use cp_microservice::{
+    core::error::{Error, ErrorKind},
+    logic::executor::{timeout_receive_storage_response, timeout_send_storage_request},
+};
+
+use crate::{
+    logic::{
+        actions::role_action::RoleAction,
+        authorization::{
+            authorize, authorize_grants, PERMISSION_CREATE_ROLES, PERMISSION_DELETE_ROLES,
+            PERMISSION_READ_ROLES, PERMISSION_UPDATE_ROLES,
+        },
+        logic_request::LogicRequest,
+        role_hierarchy::{collect_permissions, creates_cycle},
+    },
+    storage::{
+        self, role::Role, role_deletion::RoleDeletion, storage_request::StorageRequest,
+        unique_write::UniqueWrite,
+    },
+};
+
+const TIMEOUT_GET_ROLES_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_GET_ADMIN_ROLE_ID_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_LIST_ROLES_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_CREATE_ROLE_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_UPDATE_ROLE_IN_MILLISECONDS: u64 = 10000u64;
+const TIMEOUT_DELETE_ROLE_IN_MILLISECONDS: u64 = 10000u64;
+
+pub async fn execute_role_action(
+    request: LogicRequest,
+    sender: Sender<StorageRequest>,
+) -> Result<(), Error> {
+    match request {
+        LogicRequest::Role(action) => match action {
+            Some(action) => match action {
+                RoleAction::Create {
+                    org_id,
+                    name,
+                    permissions,
+                    parents,
+                    user_id,
+                    replier,
+                } => {
+                    handle_create_role(
+                        &sender,
+                        org_id,
+                        name,
+                        permissions,
+                        parents,
+                        user_id,
+                        replier,
+                    )
+                    .await
+                }
+                RoleAction::Update {
+                    id,
+                    org_id,
+                    name,
+                    permissions,
+                    parents,
+                    user_id,
+                    replier,
+                } => {
+                    handle_update_role(
+                        &sender,
+                        id,
+                        org_id,
+                        name,
+                        permissions,
+                        parents,
+                        user_id,
+                        replier,
+                    )
+                    .await
+                }
+                RoleAction::Delete {
+                    id,
+                    org_id,
+                    user_id,
+                    replier,
+                } => handle_delete_role(&sender, id, org_id, user_id, replier).await,
+                RoleAction::List {
+                    org_id,
+                    user_id,
+                    replier,
+                } => handle_list_roles(&sender, org_id, user_id, replier).await,
+            },
+            None => Err(Error::new(
+                ErrorKind::LogicError,
+                "[logic.role.execute_role_action] received 'None' as role action",
+            )),
+        },
+        _ => Err(Error::new(
+            ErrorKind::LogicError,
+            "[logic.role.execute_role_action] received an unexpected logic request",
+        )),
+    }
+}
+
+async fn handle_create_role(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    name: String,
+    permissions: Vec<String>,
+    parents: Vec<String>,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+) -> Result<(), Error> {
+    let api_replier = validate_role_input(api_replier, Some(&name), Some(&permissions))?;
+
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_CREATE_ROLES,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, roles) = validate_role_relations(
+        sender,
+        &org_id,
+        None,
+        Some(name.as_str()),
+        Some(&parents),
+        api_replier,
+    )
+    .await?;
+
+    let granted = role_grants(&roles, Some(&permissions), Some(&parents));
+
+    let mut api_replier =
+        authorize_grants(sender, &user_id, &org_id, &granted, api_replier).await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<UniqueWrite<String>, Error>>();
+
+    let storage_request =
+        StorageRequest::Role(Some(storage::actions::role_action::RoleAction::Create {
+            organization_id: org_id,
+            name,
+            permissions,
+            parents,
+            replier: storage_replier,
+        }));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_CREATE_ROLE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, write) = timeout_receive_storage_response(
+        TIMEOUT_CREATE_ROLE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    let role_id = match write {
+        UniqueWrite::Written(role_id) => role_id,
+        UniqueWrite::Duplicate => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.role.handle_create_role] a role with that name already exists in the organization",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = api_replier.send(Ok(role_id)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_update_role(
+    sender: &Sender<StorageRequest>,
+    id: String,
+    org_id: String,
+    name: Option<String>,
+    permissions: Option<Vec<String>>,
+    parents: Option<Vec<String>>,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    if name.is_none() && permissions.is_none() && parents.is_none() {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.role.handle_update_role] no fields to update were provided",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    let api_replier = validate_role_input(api_replier, name.as_ref(), permissions.as_ref())?;
+
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_UPDATE_ROLES,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, roles) = validate_role_relations(
+        sender,
+        &org_id,
+        Some(&id),
+        name.as_deref(),
+        parents.as_ref(),
+        api_replier,
+    )
+    .await?;
+
+    let granted = role_grants(&roles, permissions.as_ref(), parents.as_ref());
+
+    let mut api_replier =
+        authorize_grants(sender, &user_id, &org_id, &granted, api_replier).await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<UniqueWrite<bool>, Error>>();
+
+    let storage_request =
+        StorageRequest::Role(Some(storage::actions::role_action::RoleAction::Update {
+            id,
+            organization_id: org_id,
+            name,
+            permissions,
+            parents,
+            replier: storage_replier,
+        }));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_UPDATE_ROLE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, write) = timeout_receive_storage_response(
+        TIMEOUT_UPDATE_ROLE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    let found = match write {
+        UniqueWrite::Written(found) => found,
+        UniqueWrite::Duplicate => {
+            let error = Error::new(
+                ErrorKind::LogicError,
+                "[logic.role.handle_update_role] a role with that name already exists in the organization",
+            );
+
+            if let Err(_) = api_replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to api with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if !found {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            "[logic.role.handle_update_role] role not found",
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_delete_role(
+    sender: &Sender<StorageRequest>,
+    id: String,
+    org_id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_DELETE_ROLES,
+        api_replier,
+    )
+    .await?;
+
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<RoleDeletion, Error>>();
+
+    let storage_request =
+        StorageRequest::Role(Some(storage::actions::role_action::RoleAction::Delete {
+            id,
+            organization_id: org_id,
+            replier: storage_replier,
+        }));
+
+    let api_replier = timeout_send_storage_request(
+        TIMEOUT_DELETE_ROLE_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, role_deletion) = timeout_receive_storage_response(
+        TIMEOUT_DELETE_ROLE_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    let message = match role_deletion {
+        RoleDeletion::Deleted => None,
+        RoleDeletion::NotFound => Some("role not found".to_string()),
+        RoleDeletion::Inherited => Some("other roles inherit from the role".to_string()),
+        RoleDeletion::Held(holders) => Some(format!("role is still held by {} members", holders)),
+        RoleDeletion::Granted(granting) => Some(format!(
+            "role is still granted by {} invitation codes",
+            granting
+        )),
+    };
+
+    if let Some(message) = message {
+        let error = Error::new(
+            ErrorKind::LogicError,
+            format!("[logic.role.handle_delete_role] {}", message),
+        );
+
+        if let Err(_) = api_replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to api with an error");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = api_replier.send(Ok(())) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+async fn handle_list_roles(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    user_id: String,
+    api_replier: tokio::sync::oneshot::Sender<Result<Vec<Role>, Error>>,
+) -> Result<(), Error> {
+    let api_replier = authorize(
+        sender,
+        &user_id,
+        &org_id,
+        PERMISSION_READ_ROLES,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, roles) = list_organization_roles(sender, org_id, api_replier).await?;
+
+    if let Err(_) = api_replier.send(Ok(roles)) {
+        log::warn!("failed to reply to api with an ok");
+    }
+
+    Ok(())
+}
+
+/// Permissions a role grants through its own permissions and the ones it inherits from its
+/// parents, all of which the user defining the role must hold.
+fn role_grants(
+    roles: &[Role],
+    permissions: Option<&Vec<String>>,
+    parents: Option<&Vec<String>>,
+) -> Vec<String> {
+    let mut granted: Vec<String> = permissions.cloned().unwrap_or_default();
+
+    if let Some(parents) = parents {
+        granted.extend(collect_permissions(roles, parents));
+    }
+
+    granted
+}
+
+fn validate_role_input<T>(
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+    name: Option<&String>,
+    permissions: Option<&Vec<String>>,
+) -> Result<tokio::sync::oneshot::Sender<Result<T, Error>>, Error> {
+    let message = if name.is_some_and(|name| name.trim().is_empty()) {
+        "[logic.role.validate_role_input] role name must not be empty"
+    } else if permissions.is_some_and(|permissions| {
+        permissions
+            .iter()
+            .any(|permission| permission.trim().is_empty())
+    }) {
+        "[logic.role.validate_role_input] permissions must not be empty"
+    } else {
+        return Ok(api_replier);
+    };
+
+    let error = Error::new(ErrorKind::LogicError, message);
+
+    if let Err(_) = api_replier.send(Err(error.clone())) {
+        log::warn!("failed to reply to api with an error");
+    }
+
+    Err(error)
+}
+
+/// Checks that no other role of the organization uses the name, since global roles do not
+/// belong to any organization they are not taken into account, and that every parent is
+/// available to the organization without making the role inherit from itself. Returns the
+/// roles available to the organization.
+async fn validate_role_relations<T>(
+    sender: &Sender<StorageRequest>,
+    org_id: &str,
+    role_id: Option<&str>,
+    name: Option<&str>,
+    parents: Option<&Vec<String>>,
+    api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<(tokio::sync::oneshot::Sender<Result<T, Error>>, Vec<Role>), Error> {
+    let (api_replier, roles) =
+        list_organization_roles(sender, org_id.to_string(), api_replier).await?;
+
+    let message = if let Some(name) = name.filter(|name| {
+        roles.iter().any(|role| {
+            role.organization_id() == Some(org_id)
+                && role.name() == *name
+                && role_id != Some(role.id().as_str())
+        })
+    }) {
+        format!(
+            "[logic.role.validate_role_relations] a role named '{}' already exists in the organization",
+            name
+        )
+    } else if let Some(parent) = parents.and_then(|parents| {
+        parents
+            .iter()
+            .find(|parent| !roles.iter().any(|role| &role.id() == *parent))
+    }) {
+        format!(
+            "[logic.role.validate_role_relations] parent role '{}' does not exist",
+            parent
+        )
+    } else if role_id.is_some_and(|role_id| {
+        parents.is_some_and(|parents| creates_cycle(&roles, role_id, parents))
+    }) {
+        "[logic.role.validate_role_relations] role inheritance would create a cycle".to_string()
+    } else {
+        return Ok((api_replier, roles));
+    };
+
+    let error = Error::new(ErrorKind::LogicError, message);
+
+    if let Err(_) = api_replier.send(Err(error.clone())) {
+        log::warn!("failed to reply to api with an error");
+    }
+
+    Err(error)
+}
+
+pub async fn list_organization_roles<T>(
+    sender: &Sender<StorageRequest>,
+    org_id: String,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<(tokio::sync::oneshot::Sender<Result<T, Error>>, Vec<Role>), Error> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<Vec<Role>, Error>>();
+
+    let storage_request = StorageRequest::Role(Some(
+        storage::actions::role_action::RoleAction::ListByOrganization {
+            organization_id: org_id,
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_LIST_ROLES_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, roles) = timeout_receive_storage_response(
+        TIMEOUT_LIST_ROLES_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, roles))
+}
+
+pub async fn get_roles<T>(
+    sender: &Sender<StorageRequest>,
+    ids: Vec<String>,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<(tokio::sync::oneshot::Sender<Result<T, Error>>, Vec<Role>), Error> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<Vec<Role>, Error>>();
+
+    let storage_request =
+        StorageRequest::Role(Some(storage::actions::role_action::RoleAction::GetByIds {
+            ids,
+            replier: storage_replier,
+        }));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_GET_ROLES_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, roles) = timeout_receive_storage_response(
+        TIMEOUT_GET_ROLES_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, roles))
+}
+
+pub async fn get_admin_role_id<T>(
+    sender: &Sender<StorageRequest>,
+    mut api_replier: tokio::sync::oneshot::Sender<Result<T, Error>>,
+) -> Result<(tokio::sync::oneshot::Sender<Result<T, Error>>, String), Error> {
+    let (storage_replier, storage_receiver) =
+        tokio::sync::oneshot::channel::<Result<String, Error>>();
+
+    let storage_request = StorageRequest::Role(Some(
+        storage::actions::role_action::RoleAction::GetAdminRoleId {
+            replier: storage_replier,
+        },
+    ));
+
+    api_replier = timeout_send_storage_request(
+        TIMEOUT_GET_ADMIN_ROLE_ID_IN_MILLISECONDS,
+        storage_request,
+        sender,
+        api_replier,
+    )
+    .await?;
+
+    let (api_replier, admin_role_id) = timeout_receive_storage_response(
+        TIMEOUT_GET_ADMIN_ROLE_ID_IN_MILLISECONDS,
+        storage_receiver,
+        api_replier,
+    )
+    .await?;
+
+    Ok((api_replier, admin_role_id))
+}
+
+#[cfg(test)]
+use crate::{
+    logic::test_storage::{
+        create_test_member, create_test_organization, create_test_roles, spawn_storage,
+        TEST_ADMIN_ROLE_ID, TEST_MEMBER_ROLE_ID, TEST_ORGANIZATION_ID,
+    },
+    storage::actions::{
+        member_action::MemberAction, organization_action::OrganizationAction,
+        role_action::RoleAction as StorageRoleAction,
+    },
+};
+#[cfg(test)]
+use bson::doc;
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+#[cfg(test)]
+const TEST_CUSTOM_ROLE_ID: &str = "653846b428c2649821284c53";
+
+/// Answers role requests of an organization holding the global roles plus the custom role
+/// 'reviewers', recording whether any role was written. 'gabriel' is an admin whereas 'editor'
+/// holds the member role and may manage roles. Creating 'racers' loses a race against a
+/// concurrent creation with the same name, so storage reports it as a duplicate. Deleting any
+/// of `invitation_code_roles` is refused as an invitation code still grants it.
+#[cfg(test)]
+fn spawn_role_storage(
+    invitation_code_roles: Vec<&'static str>,
+    written: Arc<AtomicBool>,
+) -> async_channel::Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::Member(Some(MemberAction::Get {
+            user_id, replier, ..
+        })) => {
+            let member = match user_id.as_str() {
+                "gabriel" => {
+                    create_test_member("gabriel", vec![TEST_ADMIN_ROLE_ID], vec![], vec![])
+                }
+                _ => create_test_member(
+                    &user_id,
+                    vec![TEST_MEMBER_ROLE_ID],
+                    vec!["org:roles:*"],
+                    vec![],
+                ),
+            };
+
+            replier.send(Ok(Some(member))).unwrap();
+        }
+        StorageRequest::Role(Some(StorageRoleAction::ListByOrganization { replier, .. })) => {
+            let mut roles = create_test_roles();
+            roles.push(
+                bson::from_document(doc! {
+                    "_id": bson::oid::ObjectId::parse_str(TEST_CUSTOM_ROLE_ID).unwrap(),
+                    "name": "reviewers",
+                    "permissions": ["org:members:read"],
+                    "organization_id": TEST_ORGANIZATION_ID
+                })
+                .unwrap(),
+            );
+
+            replier.send(Ok(roles)).unwrap();
+        }
+        StorageRequest::Organization(Some(OrganizationAction::Get { replier, .. })) => {
+            replier
+                .send(Ok(Some(create_test_organization("gabriel"))))
+                .unwrap();
+        }
+        StorageRequest::Role(Some(StorageRoleAction::Create { name, replier, .. })) => {
+            let write = match name.as_str() {
+                "racers" => UniqueWrite::Duplicate,
+                _ => {
+                    written.store(true, Ordering::SeqCst);
+                    UniqueWrite::Written(TEST_CUSTOM_ROLE_ID.to_string())
+                }
+            };
+
+            replier.send(Ok(write)).unwrap();
+        }
+        StorageRequest::Role(Some(StorageRoleAction::Update { replier, .. })) => {
+            written.store(true, Ordering::SeqCst);
+            replier.send(Ok(UniqueWrite::Written(true))).unwrap();
+        }
+        StorageRequest::Role(Some(StorageRoleAction::Delete { id, replier, .. })) => {
+            let role_deletion = if invitation_code_roles.contains(&id.as_str()) {
+                RoleDeletion::Granted(1u64)
+            } else {
+                written.store(true, Ordering::SeqCst);
+                RoleDeletion::Deleted
+            };
+
+            replier.send(Ok(role_deletion)).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+async fn create_role(
+    user_id: &str,
+    name: &str,
+    permissions: Vec<&str>,
+    parents: Vec<&str>,
+) -> (Result<String, Error>, bool) {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_role_storage(vec![], written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_create_role(
+        &sender,
+        TEST_ORGANIZATION_ID.to_string(),
+        name.to_string(),
+        permissions
+            .iter()
+            .map(|permission| permission.to_string())
+            .collect(),
+        parents.iter().map(|parent| parent.to_string()).collect(),
+        user_id.to_string(),
+        api_replier,
+    )
+    .await;
+
+    (api_receiver.await.unwrap(), written.load(Ordering::SeqCst))
+}
+
+#[test]
+pub fn validate_role_input_accepts_missing_fields() {
+    let (api_replier, _api_receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    assert!(validate_role_input(api_replier, None, None).is_ok());
+}
+
+#[test]
+pub fn validate_role_input_rejects_blank_name() {
+    let (api_replier, _api_receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    assert!(validate_role_input(api_replier, Some(&"  ".to_string()), None).is_err());
+}
+
+#[test]
+pub fn validate_role_input_rejects_blank_permission() {
+    let (api_replier, _api_receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+    let permissions = vec!["org:read".to_string(), " ".to_string()];
+
+    assert!(validate_role_input(
+        api_replier,
+        Some(&"viewers".to_string()),
+        Some(&permissions)
+    )
+    .is_err());
+}
+
+#[tokio::test]
+pub async fn create_role_creates_role_with_held_permissions() {
+    let (result, written) = create_role(
+        "editor",
+        "readers",
+        vec!["org:read"],
+        vec![TEST_MEMBER_ROLE_ID],
+    )
+    .await;
+
+    assert_eq!(TEST_CUSTOM_ROLE_ID, result.unwrap());
+    assert!(written);
+}
+
+#[tokio::test]
+pub async fn create_role_rejects_name_taken_within_organization() {
+    let (result, written) = create_role("gabriel", "reviewers", vec!["org:read"], vec![]).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn create_role_rejects_name_taken_concurrently() {
+    let (result, written) = create_role("gabriel", "racers", vec!["org:read"], vec![]).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn create_role_rejects_permissions_not_held_by_caller() {
+    let (result, written) = create_role("editor", "deleters", vec!["org:delete"], vec![]).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn create_role_rejects_inheriting_permissions_not_held_by_caller() {
+    let (result, written) = create_role("editor", "admins", vec![], vec![TEST_ADMIN_ROLE_ID]).await;
+
+    assert!(result.is_err());
+    assert!(!written);
+}
+
+#[tokio::test]
+pub async fn update_role_rejects_permissions_not_held_by_caller() {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_role_storage(vec![], written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_update_role(
+        &sender,
+        TEST_CUSTOM_ROLE_ID.to_string(),
+        TEST_ORGANIZATION_ID.to_string(),
+        None,
+        Some(vec!["*".to_string()]),
+        None,
+        "editor".to_string(),
+        api_replier,
+    )
+    .await;
+
+    assert!(api_receiver.await.unwrap().is_err());
+    assert!(!written.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+pub async fn delete_role_refuses_role_granted_by_invitation_code() {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_role_storage(vec![TEST_CUSTOM_ROLE_ID], written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_delete_role(
+        &sender,
+        TEST_CUSTOM_ROLE_ID.to_string(),
+        TEST_ORGANIZATION_ID.to_string(),
+        "gabriel".to_string(),
+        api_replier,
+    )
+    .await;
+
+    assert!(api_receiver.await.unwrap().is_err());
+    assert!(!written.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+pub async fn delete_role_deletes_role_no_longer_referenced() {
+    let written = Arc::new(AtomicBool::new(false));
+    let sender = spawn_role_storage(vec![TEST_MEMBER_ROLE_ID], written.clone());
+    let (api_replier, api_receiver) = tokio::sync::oneshot::channel();
+
+    let _ = handle_delete_role(
+        &sender,
+        TEST_CUSTOM_ROLE_ID.to_string(),
+        TEST_ORGANIZATION_ID.to_string(),
+        "gabriel".to_string(),
+        api_replier,
+    )
+    .await;
+
+    assert!(api_receiver.await.unwrap().is_ok());
+    assert!(written.load(Ordering::SeqCst));
+}
@@ -5,7 +5,9 @@ use cp_microservice::core::error::Error;
 
 use crate::{logic::logic_request::LogicRequest, storage::storage_request::StorageRequest};
 
-pub fn get_logic_executors() -> HashMap<
+pub fn get_logic_executors(
+    transactions_enabled: bool,
+) -> HashMap<
     Discriminant<LogicRequest>,
     Arc<
         dyn Fn(
@@ -32,9 +34,13 @@ pub fn get_logic_executors() -> HashMap<
     executors.insert(
         std::mem::discriminant(&LogicRequest::Organization(None)),
         Arc::new(move |request, sender| {
-            Box::pin(crate::logic::executors::organization::create_organization(
-                request, sender,
-            ))
+            Box::pin(
+                crate::logic::executors::organization::execute_organization_action(
+                    request,
+                    sender,
+                    transactions_enabled,
+                ),
+            )
         }),
     );
 
@@ -42,10 +48,32 @@ pub fn get_logic_executors() -> HashMap<
         std::mem::discriminant(&LogicRequest::InvitationCode(None)),
         Arc::new(move |request, sender| {
             Box::pin(
-                crate::logic::executors::invitation_code::create_invitation_code(request, sender),
+                crate::logic::executors::invitation_code::execute_invitation_code_action(
+                    request, sender,
+                ),
             )
         }),
     );
 
+    executors.insert(
+        std::mem::discriminant(&LogicRequest::Member(None)),
+        Arc::new(move |request, sender| {
+            Box::pin(crate::logic::executors::member::execute_member_action(
+                request,
+                sender,
+                transactions_enabled,
+            ))
+        }),
+    );
+
+    executors.insert(
+        std::mem::discriminant(&LogicRequest::Role(None)),
+        Arc::new(move |request, sender| {
+            Box::pin(crate::logic::executors::role::execute_role_action(
+                request, sender,
+            ))
+        }),
+    );
+
     executors
 }
@@ -1,9 +1,12 @@
 use crate::logic::actions::{
-    invitation_code_action::InvitationCodeAction, organization_action::OrganizationAction,
+    invitation_code_action::InvitationCodeAction, member_action::MemberAction,
+    organization_action::OrganizationAction, role_action::RoleAction,
 };
 
 #[derive(Debug)]
 pub enum LogicRequest {
     Organization(Option<OrganizationAction>),
     InvitationCode(Option<InvitationCodeAction>),
+    Member(Option<MemberAction>),
+    Role(Option<RoleAction>),
 }
@@ -0,0 +1,67 @@
+/// Computes the roles a member ends up with. `roles` replaces the current roles as a whole,
+/// otherwise `add` and `remove` are applied on top of them. Duplicates are dropped while the
+/// order of the remaining roles is kept.
+pub fn apply_role_changes(
+    current: &[String],
+    roles: Option<Vec<String>>,
+    add: &[String],
+    remove: &[String],
+) -> Vec<String> {
+    let candidates: Vec<String> = match roles {
+        Some(roles) => roles,
+        None => current.iter().chain(add.iter()).cloned().collect(),
+    };
+
+    let mut result: Vec<String> = Vec::new();
+
+    for role in candidates {
+        if remove.contains(&role) || result.contains(&role) {
+            continue;
+        }
+
+        result.push(role);
+    }
+
+    result
+}
+
+#[cfg(test)]
+fn to_roles(roles: &[&str]) -> Vec<String> {
+    roles.iter().map(|role| role.to_string()).collect()
+}
+
+#[test]
+pub fn replaces_current_roles() {
+    let result = apply_role_changes(
+        &to_roles(&["admin", "member"]),
+        Some(to_roles(&["member", "viewer"])),
+        &[],
+        &[],
+    );
+
+    assert_eq!(to_roles(&["member", "viewer"]), result);
+}
+
+#[test]
+pub fn adds_and_removes_roles() {
+    let result = apply_role_changes(
+        &to_roles(&["admin", "member"]),
+        None,
+        &to_roles(&["viewer"]),
+        &to_roles(&["admin"]),
+    );
+
+    assert_eq!(to_roles(&["member", "viewer"]), result);
+}
+
+#[test]
+pub fn drops_duplicated_roles() {
+    let result = apply_role_changes(
+        &to_roles(&["member"]),
+        None,
+        &to_roles(&["member", "viewer", "viewer"]),
+        &[],
+    );
+
+    assert_eq!(to_roles(&["member", "viewer"]), result);
+}
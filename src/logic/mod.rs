@@ -1,4 +1,11 @@
 pub mod actions;
+pub mod authorization;
 pub mod executors;
 pub mod logic_executors;
 pub mod logic_request;
+pub mod member_roles;
+pub mod permission;
+pub mod role_hierarchy;
+pub mod saga;
+#[cfg(test)]
+pub mod test_storage;
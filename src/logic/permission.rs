@@ -0,0 +1,105 @@
+const SEGMENT_SEPARATOR: char = ':';
+const WILDCARD: &str = "*";
+
+/// Checks whether a granted permission covers the required one. Permissions are hierarchical
+/// names whose segments are separated by `:`; a `*` segment matches any single segment, or any
+/// number of remaining segments when it is the last one (so `*` alone matches everything).
+pub fn matches(granted: &str, required: &str) -> bool {
+    let mut granted_segments = granted.split(SEGMENT_SEPARATOR).peekable();
+    let mut required_segments = required.split(SEGMENT_SEPARATOR);
+
+    loop {
+        match (granted_segments.next(), required_segments.next()) {
+            (Some(WILDCARD), Some(_)) if granted_segments.peek().is_none() => return true,
+            (Some(WILDCARD), Some(_)) => continue,
+            (Some(granted_segment), Some(required_segment))
+                if granted_segment == required_segment =>
+            {
+                continue
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+pub fn is_granted(permissions: &[String], required: &str) -> bool {
+    permissions.iter().any(|granted| matches(granted, required))
+}
+
+/// Permissions held by a member. Denials take precedence over grants, no matter whether these
+/// come from the member's roles or were granted to the member directly.
+#[derive(Debug)]
+pub struct EffectivePermissions {
+    granted: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl EffectivePermissions {
+    pub fn new(granted: Vec<String>, denied: Vec<String>) -> Self {
+        Self { granted, denied }
+    }
+
+    pub fn allows(&self, required: &str) -> bool {
+        is_granted(&self.granted, required) && !is_granted(&self.denied, required)
+    }
+}
+
+#[test]
+pub fn exact_permission_matches() {
+    assert!(matches("org:members:invite", "org:members:invite"));
+    assert!(!matches("org:members:invite", "org:members:remove"));
+}
+
+#[test]
+pub fn permission_does_not_match_its_parent_or_children() {
+    assert!(!matches("org:members", "org:members:invite"));
+    assert!(!matches("org:members:invite", "org:members"));
+}
+
+#[test]
+pub fn trailing_wildcard_matches_every_descendant() {
+    assert!(matches("org:members:*", "org:members:invite"));
+    assert!(matches("org:members:*", "org:members:invite:resend"));
+    assert!(!matches("org:members:*", "org:members"));
+    assert!(!matches("org:members:*", "org:invitations:read"));
+}
+
+#[test]
+pub fn inner_wildcard_matches_a_single_segment() {
+    assert!(matches("org:*:read", "org:invitations:read"));
+    assert!(!matches("org:*:read", "org:invitations:revoke"));
+    assert!(!matches("org:*:read", "org:read"));
+}
+
+#[test]
+pub fn lone_wildcard_matches_everything() {
+    assert!(matches("*", "org:read"));
+    assert!(matches("*", "org:members:invite"));
+}
+
+#[test]
+pub fn is_granted_when_any_permission_matches() {
+    let permissions = vec!["org:read".to_string(), "org:invitations:*".to_string()];
+
+    assert!(is_granted(&permissions, "org:invitations:revoke"));
+    assert!(!is_granted(&permissions, "org:update"));
+}
+
+#[test]
+pub fn denial_overrides_wildcard_grant() {
+    let permissions =
+        EffectivePermissions::new(vec!["*".to_string()], vec!["org:delete".to_string()]);
+
+    assert!(permissions.allows("org:update"));
+    assert!(!permissions.allows("org:delete"));
+}
+
+#[test]
+pub fn wildcard_denial_covers_every_descendant() {
+    let permissions =
+        EffectivePermissions::new(vec!["org:*".to_string()], vec!["org:members:*".to_string()]);
+
+    assert!(permissions.allows("org:read"));
+    assert!(!permissions.allows("org:members:remove"));
+}
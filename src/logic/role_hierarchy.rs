@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::storage::role::Role;
+
+/// Collects the permissions of the given roles and of all their ancestors. Unknown roles are
+/// ignored and every role is visited once, so inconsistent data can not loop forever.
+pub fn collect_permissions(roles: &[Role], role_ids: &[String]) -> Vec<String> {
+    let mut permissions: Vec<String> = Vec::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut pending: Vec<String> = role_ids.to_vec();
+
+    while let Some(role_id) = pending.pop() {
+        if !visited.insert(role_id.clone()) {
+            continue;
+        }
+
+        let role = match roles.iter().find(|role| role.id() == role_id) {
+            Some(role) => role,
+            None => continue,
+        };
+
+        permissions.extend_from_slice(role.permissions());
+        pending.extend_from_slice(role.parents());
+    }
+
+    permissions
+}
+
+/// Checks whether making the given parents the parents of the role would let the role inherit
+/// from itself.
+pub fn creates_cycle(roles: &[Role], role_id: &str, parents: &[String]) -> bool {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut pending: Vec<String> = parents.to_vec();
+
+    while let Some(ancestor_id) = pending.pop() {
+        if ancestor_id == role_id {
+            return true;
+        }
+
+        if !visited.insert(ancestor_id.clone()) {
+            continue;
+        }
+
+        if let Some(ancestor) = roles.iter().find(|role| role.id() == ancestor_id) {
+            pending.extend_from_slice(ancestor.parents());
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+use bson::doc;
+
+#[cfg(test)]
+const MEMBER_ROLE_ID: &str = "653846b428c2649821284c60";
+#[cfg(test)]
+const MANAGER_ROLE_ID: &str = "653846b428c2649821284c61";
+#[cfg(test)]
+const DIRECTOR_ROLE_ID: &str = "653846b428c2649821284c62";
+
+#[cfg(test)]
+fn create_test_role(id: &str, permissions: Vec<&str>, parents: Vec<&str>) -> Role {
+    bson::from_document(doc! {
+        "_id": bson::oid::ObjectId::parse_str(id).unwrap(),
+        "name": id,
+        "permissions": permissions,
+        "parents": parents
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+fn create_test_roles() -> Vec<Role> {
+    vec![
+        create_test_role(MEMBER_ROLE_ID, vec!["org:read"], vec![]),
+        create_test_role(MANAGER_ROLE_ID, vec!["org:invite"], vec![MEMBER_ROLE_ID]),
+        create_test_role(DIRECTOR_ROLE_ID, vec!["org:update"], vec![MANAGER_ROLE_ID]),
+    ]
+}
+
+#[test]
+pub fn collects_permissions_of_every_ancestor() {
+    let roles = create_test_roles();
+
+    let mut permissions = collect_permissions(&roles, &[DIRECTOR_ROLE_ID.to_string()]);
+    permissions.sort();
+
+    assert_eq!(vec!["org:invite", "org:read", "org:update"], permissions);
+}
+
+#[test]
+pub fn ignores_unknown_roles() {
+    let roles = create_test_roles();
+
+    let permissions = collect_permissions(&roles, &["unknown".to_string()]);
+
+    assert!(permissions.is_empty());
+}
+
+#[test]
+pub fn terminates_on_existing_cycles() {
+    let roles = vec![
+        create_test_role(MEMBER_ROLE_ID, vec!["org:read"], vec![MANAGER_ROLE_ID]),
+        create_test_role(MANAGER_ROLE_ID, vec!["org:invite"], vec![MEMBER_ROLE_ID]),
+    ];
+
+    let permissions = collect_permissions(&roles, &[MEMBER_ROLE_ID.to_string()]);
+
+    assert_eq!(2, permissions.len());
+}
+
+#[test]
+pub fn detects_cycle_through_ancestors() {
+    let roles = create_test_roles();
+
+    assert!(creates_cycle(
+        &roles,
+        MEMBER_ROLE_ID,
+        &[DIRECTOR_ROLE_ID.to_string()]
+    ));
+    assert!(creates_cycle(
+        &roles,
+        MEMBER_ROLE_ID,
+        &[MEMBER_ROLE_ID.to_string()]
+    ));
+}
+
+#[test]
+pub fn accepts_acyclic_parents() {
+    let roles = create_test_roles();
+
+    assert!(!creates_cycle(
+        &roles,
+        DIRECTOR_ROLE_ID,
+        &[MEMBER_ROLE_ID.to_string()]
+    ));
+}
@@ -0,0 +1,216 @@
+use std::{future::Future, time::Duration};
+
+use async_channel::Sender;
+use cp_microservice::core::error::Error;
+use tokio::time::timeout;
+
+use crate::storage::storage_request::StorageRequest;
+
+const TIMEOUT_COMPENSATION_IN_MILLISECONDS: u64 = 10000u64;
+
+type Compensation = Box<
+    dyn FnOnce(tokio::sync::oneshot::Sender<Result<(), Error>>) -> StorageRequest + Send + Sync,
+>;
+
+/// Chains the storage steps of a logic flow and, whenever one of them fails or times out,
+/// undoes the steps run so far, the failed one included, by running their compensating storage
+/// actions in reverse order.
+pub struct Saga {
+    sender: Sender<StorageRequest>,
+    compensations: Vec<Compensation>,
+}
+
+impl Saga {
+    pub fn new(sender: &Sender<StorageRequest>) -> Self {
+        Self {
+            sender: sender.clone(),
+            compensations: Vec::new(),
+        }
+    }
+
+    /// Registers the storage action which undoes the next step. It is registered before the step
+    /// runs because a step timing out may still have been applied, so the action must have no
+    /// effect when the step was not applied and must be safe to run more than once.
+    pub fn register_compensation(
+        &mut self,
+        compensation: impl FnOnce(tokio::sync::oneshot::Sender<Result<(), Error>>) -> StorageRequest
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.compensations.push(Box::new(compensation));
+    }
+
+    /// Awaits the given step, compensating every registered step if it fails.
+    pub async fn step<T>(
+        &mut self,
+        step: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, Error> {
+        match step.await {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                self.compensate().await;
+
+                Err(error)
+            }
+        }
+    }
+
+    pub async fn compensate(&mut self) {
+        while let Some(compensation) = self.compensations.pop() {
+            let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
+
+            let storage_request = compensation(replier);
+
+            match timeout(
+                Duration::from_millis(TIMEOUT_COMPENSATION_IN_MILLISECONDS),
+                self.sender.send(storage_request),
+            )
+            .await
+            {
+                Ok(Ok(_)) => (),
+                Ok(Err(error)) => {
+                    log::error!("failed to send compensating storage request: {}", &error);
+                    continue;
+                }
+                Err(_) => {
+                    log::error!("timed out sending compensating storage request");
+                    continue;
+                }
+            }
+
+            match timeout(
+                Duration::from_millis(TIMEOUT_COMPENSATION_IN_MILLISECONDS),
+                receiver,
+            )
+            .await
+            {
+                Ok(Ok(Ok(_))) => (),
+                Ok(Ok(Err(error))) => {
+                    log::error!("compensating storage action failed: {}", &error);
+                }
+                Ok(Err(error)) => {
+                    log::error!(
+                        "failed to receive compensating storage response: {}",
+                        &error
+                    );
+                }
+                Err(_) => {
+                    log::error!("timed out waiting for compensating storage response");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::{
+    logic::test_storage::spawn_storage,
+    storage::actions::invitation_code_action::InvitationCodeAction,
+};
+#[cfg(test)]
+use cp_microservice::core::error::ErrorKind;
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Answers compensations releasing invitation codes, recording the released codes in order and
+/// failing the release of 'failing'.
+#[cfg(test)]
+fn spawn_compensation_storage(released: Arc<Mutex<Vec<String>>>) -> Sender<StorageRequest> {
+    spawn_storage(move |storage_request| match storage_request {
+        StorageRequest::InvitationCode(Some(InvitationCodeAction::Release {
+            code,
+            replier,
+            ..
+        })) => {
+            let result = if code == "failing" {
+                Err(Error::new(ErrorKind::StorageError, "release failed"))
+            } else {
+                Ok(())
+            };
+
+            released.lock().unwrap().push(code);
+            replier.send(result).unwrap();
+        }
+        _ => panic!("unexpected storage request"),
+    })
+}
+
+#[cfg(test)]
+fn register_release(saga: &mut Saga, code: &str) {
+    let code = code.to_string();
+
+    saga.register_compensation(move |replier| {
+        StorageRequest::InvitationCode(Some(InvitationCodeAction::Release {
+            code,
+            redemption_id: "redemption".to_string(),
+            replier,
+        }))
+    });
+}
+
+#[tokio::test]
+pub async fn successful_steps_are_not_compensated() {
+    let released = Arc::new(Mutex::new(Vec::new()));
+    let sender = spawn_compensation_storage(released.clone());
+    let mut saga = Saga::new(&sender);
+
+    register_release(&mut saga, "first");
+    let first = saga.step(async { Ok::<u32, Error>(1) }).await;
+    let second = saga.step(async { Ok::<u32, Error>(2) }).await;
+
+    assert_eq!(1, first.unwrap());
+    assert_eq!(2, second.unwrap());
+    assert!(released.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+pub async fn failed_step_compensates_previous_steps_in_reverse_order() {
+    let released = Arc::new(Mutex::new(Vec::new()));
+    let sender = spawn_compensation_storage(released.clone());
+    let mut saga = Saga::new(&sender);
+
+    register_release(&mut saga, "first");
+    register_release(&mut saga, "second");
+
+    let result = saga
+        .step(async { Err::<(), Error>(Error::new(ErrorKind::StorageError, "step failed")) })
+        .await;
+
+    assert_eq!("step failed", result.unwrap_err().message);
+    assert_eq!(
+        vec!["second".to_string(), "first".to_string()],
+        *released.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+pub async fn failed_compensation_does_not_stop_remaining_ones() {
+    let released = Arc::new(Mutex::new(Vec::new()));
+    let sender = spawn_compensation_storage(released.clone());
+    let mut saga = Saga::new(&sender);
+
+    register_release(&mut saga, "first");
+    register_release(&mut saga, "failing");
+
+    saga.compensate().await;
+
+    assert_eq!(
+        vec!["failing".to_string(), "first".to_string()],
+        *released.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+pub async fn compensations_run_only_once() {
+    let released = Arc::new(Mutex::new(Vec::new()));
+    let sender = spawn_compensation_storage(released.clone());
+    let mut saga = Saga::new(&sender);
+
+    register_release(&mut saga, "first");
+
+    saga.compensate().await;
+    saga.compensate().await;
+
+    assert_eq!(vec!["first".to_string()], *released.lock().unwrap());
+}
@@ -0,0 +1,81 @@
+use async_channel::Sender;
+use bson::doc;
+
+use crate::storage::{
+    member::Member, organization::Organization, role::Role, storage_request::StorageRequest,
+};
+
+pub const TEST_ORGANIZATION_ID: &str = "653846b428c2649821284c50";
+pub const TEST_ADMIN_ROLE_ID: &str = "653846b428c2649821284c51";
+pub const TEST_MEMBER_ROLE_ID: &str = "653846b428c2649821284c52";
+
+/// Spawns a storage stand-in answering every request through `handler` until all senders are
+/// dropped.
+pub fn spawn_storage<F>(mut handler: F) -> Sender<StorageRequest>
+where
+    F: FnMut(StorageRequest) + Send + 'static,
+{
+    let (sender, receiver) = async_channel::unbounded::<StorageRequest>();
+
+    tokio::spawn(async move {
+        while let Ok(storage_request) = receiver.recv().await {
+            handler(storage_request);
+        }
+    });
+
+    sender
+}
+
+pub fn create_test_organization(owner: &str) -> Organization {
+    bson::from_document(doc! {
+        "_id": bson::oid::ObjectId::parse_str(TEST_ORGANIZATION_ID).unwrap(),
+        "country": "es",
+        "name": "example",
+        "address": {
+            "country": "es",
+            "region": "albacete",
+            "city": "villarrobledo",
+            "street": "calle molino estrada",
+            "number": "37",
+            "additional": "",
+            "postal_code": "02600"
+        },
+        "owner": owner
+    })
+    .unwrap()
+}
+
+pub fn create_test_member(
+    user_id: &str,
+    roles: Vec<&str>,
+    permissions: Vec<&str>,
+    denied_permissions: Vec<&str>,
+) -> Member {
+    bson::from_document(doc! {
+        "_id": bson::oid::ObjectId::new(),
+        "user_id": user_id,
+        "organization_id": TEST_ORGANIZATION_ID,
+        "roles": roles,
+        "permissions": permissions,
+        "denied_permissions": denied_permissions
+    })
+    .unwrap()
+}
+
+pub fn create_test_role(id: &str, permissions: Vec<&str>, default_admin: bool) -> Role {
+    bson::from_document(doc! {
+        "_id": bson::oid::ObjectId::parse_str(id).unwrap(),
+        "name": id,
+        "permissions": permissions,
+        "default_admin": default_admin,
+        "default_member": !default_admin
+    })
+    .unwrap()
+}
+
+pub fn create_test_roles() -> Vec<Role> {
+    vec![
+        create_test_role(TEST_ADMIN_ROLE_ID, vec!["*"], true),
+        create_test_role(TEST_MEMBER_ROLE_ID, vec!["org:read"], false),
+    ]
+}
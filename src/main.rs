@@ -13,19 +13,18 @@ use cp_microservice::{
 };
 use simple_logger::SimpleLogger;
 
-use crate::{
+use cp_organization::{
     api::{api_actions::get_api_actions, api_plugins::get_api_plugins},
-    init::{get_amqp_api, get_amqp_connection_config, get_mongodb_client, get_secrets_manager},
+    init::{
+        get_amqp_api, get_amqp_connection_config, get_default_admin_permissions,
+        get_default_member_permissions, get_deleted_organization_grace_period,
+        get_json_web_key_set, get_mongodb_client, get_mongodb_transactions_enabled,
+        get_openid_connect_config, get_secrets_manager,
+    },
     logic::{logic_executors::get_logic_executors, logic_request::LogicRequest},
     storage::storage_request::StorageRequest,
 };
 
-pub mod api;
-pub mod error;
-pub mod init;
-pub mod logic;
-pub mod storage;
-
 #[tokio::main]
 pub async fn main() -> Result<(), Error> {
     SimpleLogger::new().init().unwrap();
@@ -35,20 +34,53 @@ pub async fn main() -> Result<(), Error> {
 
     let secrets_manager: Arc<dyn SecretsManager> = get_secrets_manager()?;
 
+    // Storage is migrated and seeded before the API starts consuming requests, so none is
+    // served against an outdated schema or without the default roles.
+    let storage_connection = get_mongodb_client(&secrets_manager)?;
+
+    let migrator = cp_organization::storage::migrations::migrator::Migrator::new(
+        storage_connection.clone(),
+        cp_organization::storage::migrations::get_migrations(),
+    );
+
+    if let Err(error) = migrator.run_pending().await {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("failed to run storage migrations: {}", &error),
+        ));
+    }
+
+    if let Err(error) = cp_organization::storage::default_roles::seed_default_roles(
+        &storage_connection,
+        &get_default_admin_permissions()?,
+        &get_default_member_permissions()?,
+    )
+    .await
+    {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("failed to seed default roles: {}", &error),
+        ));
+    }
+
     let amqp_connection_config = get_amqp_connection_config(&secrets_manager)?;
     let amqp_api = get_amqp_api()?;
 
+    let openid_connect_config = get_openid_connect_config()?;
+    let json_web_key_set = get_json_web_key_set(&openid_connect_config).await?;
+
     let api_actions: HashMap<String, Action<LogicRequest>> = get_api_actions();
 
-    let api_plugins: Vec<Arc<dyn InputPlugin + Send + Sync>> = match get_api_plugins().await {
-        Ok(api_plugins) => api_plugins,
-        Err(error) => {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("failed to get API plugins: {}", &error),
-            ))
-        }
-    };
+    let api_plugins: Vec<Arc<dyn InputPlugin + Send + Sync>> =
+        match get_api_plugins(&openid_connect_config, json_web_key_set).await {
+            Ok(api_plugins) => api_plugins,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("failed to get API plugins: {}", &error),
+                ))
+            }
+        };
 
     let api_initialization_package = ApiInitializationPackage::<LogicRequest> {
         amqp_connection_config,
@@ -57,7 +89,7 @@ pub async fn main() -> Result<(), Error> {
         plugins: api_plugins,
     };
 
-    let logic_executors = get_logic_executors();
+    let logic_executors = get_logic_executors(get_mongodb_transactions_enabled()?);
 
     let (storage_request_sender, storage_request_receiver) =
         async_channel::bounded::<StorageRequest>(1024usize);
@@ -74,9 +106,14 @@ pub async fn main() -> Result<(), Error> {
         Err(error) => return Err(error),
     };
 
-    let storage_connection = get_mongodb_client(&secrets_manager)?;
+    let organization_purge = cp_organization::storage::organization_purge::OrganizationPurge::new(
+        storage_connection.clone(),
+        get_deleted_organization_grace_period()?,
+    );
+
+    tokio::spawn(organization_purge.run());
 
-    let storage_dispatch = crate::storage::dispatch::Dispatch::new(
+    let storage_dispatch = cp_organization::storage::dispatch::Dispatch::new(
         storage_request_receiver.clone(),
         storage_connection.clone(),
     );
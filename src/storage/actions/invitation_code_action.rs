@@ -1,12 +1,38 @@
+use bson::DateTime;
 use cp_microservice::core::error::Error;
 
+use crate::storage::{invitation_code::InvitationCode, unique_write::UniqueWrite};
+
 #[derive(Debug)]
 pub enum InvitationCodeAction {
     Create {
         code: String,
         org_id: String,
         permissions: Vec<String>,
+        denied_permissions: Vec<String>,
         roles: Vec<String>,
-        replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+        expires_at: Option<DateTime>,
+        max_uses: Option<u32>,
+        replier: tokio::sync::oneshot::Sender<Result<UniqueWrite<String>, Error>>,
+    },
+    Consume {
+        code: String,
+        user_id: String,
+        redemption_id: String,
+        replier: tokio::sync::oneshot::Sender<Result<Option<InvitationCode>, Error>>,
+    },
+    ListByOrganization {
+        org_id: String,
+        replier: tokio::sync::oneshot::Sender<Result<Vec<InvitationCode>, Error>>,
+    },
+    Revoke {
+        org_id: String,
+        code: String,
+        replier: tokio::sync::oneshot::Sender<Result<bool, Error>>,
+    },
+    Release {
+        code: String,
+        redemption_id: String,
+        replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
     },
 }
@@ -1,6 +1,14 @@
 use cp_microservice::core::error::Error;
 use tokio::sync::oneshot::Sender;
 
+use crate::storage::{
+    member::Member,
+    member_page::{MemberPage, MemberSortField},
+    member_write::{MemberRolesChange, MemberWrite},
+    unique_write::UniqueWrite,
+    user_organization::UserOrganization,
+};
+
 #[derive(Debug)]
 pub enum MemberAction {
     Create {
@@ -9,4 +17,66 @@ pub enum MemberAction {
         organization_id: String,
         replier: Sender<Result<(), Error>>,
     },
+    Join {
+        user_id: String,
+        organization_id: String,
+        roles: Vec<String>,
+        permissions: Vec<String>,
+        denied_permissions: Vec<String>,
+        replier: Sender<Result<UniqueWrite<()>, Error>>,
+    },
+    Get {
+        user_id: String,
+        organization_id: String,
+        replier: Sender<Result<Option<Member>, Error>>,
+    },
+    UpdateRoles {
+        user_id: String,
+        organization_id: String,
+        change: MemberRolesChange,
+        admin_role_id: String,
+        within_transaction: bool,
+        replier: Sender<Result<MemberWrite, Error>>,
+    },
+    UpdatePermissions {
+        user_id: String,
+        organization_id: String,
+        permissions: Option<Vec<String>>,
+        denied_permissions: Option<Vec<String>>,
+        admin_role_id: String,
+        within_transaction: bool,
+        replier: Sender<Result<MemberWrite, Error>>,
+    },
+    AddRole {
+        user_id: String,
+        organization_id: String,
+        role_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    RemoveRole {
+        user_id: String,
+        organization_id: String,
+        role_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Delete {
+        user_id: String,
+        organization_id: String,
+        admin_role_id: String,
+        within_transaction: bool,
+        replier: Sender<Result<MemberWrite, Error>>,
+    },
+    ListByOrganization {
+        organization_id: String,
+        role_id: Option<String>,
+        sort_field: MemberSortField,
+        descending: bool,
+        cursor: Option<String>,
+        limit: u32,
+        replier: Sender<Result<MemberPage, Error>>,
+    },
+    ListUserOrganizations {
+        user_id: String,
+        replier: Sender<Result<Vec<UserOrganization>, Error>>,
+    },
 }
@@ -2,12 +2,61 @@ use cp_core::geolocalization::address::Address;
 use cp_microservice::core::error::Error;
 use tokio::sync::oneshot::Sender;
 
+use crate::storage::organization::Organization;
+
 #[derive(Debug)]
 pub enum OrganizationAction {
     Create {
+        id: String,
         country: String,
         name: String,
         address: Address,
+        owner: String,
         replier: Sender<Result<String, Error>>,
     },
+    CreateWithAdmin {
+        country: String,
+        name: String,
+        address: Address,
+        user_id: String,
+        admin_role_id: String,
+        replier: Sender<Result<String, Error>>,
+    },
+    Get {
+        id: String,
+        replier: Sender<Result<Option<Organization>, Error>>,
+    },
+    Update {
+        id: String,
+        country: Option<String>,
+        name: Option<String>,
+        address: Option<Address>,
+        replier: Sender<Result<bool, Error>>,
+    },
+    Delete {
+        id: String,
+        replier: Sender<Result<bool, Error>>,
+    },
+    Restore {
+        id: String,
+        replier: Sender<Result<bool, Error>>,
+    },
+    SetOwner {
+        id: String,
+        previous_owner: String,
+        owner: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    TransferOwnership {
+        id: String,
+        previous_owner: String,
+        new_owner: String,
+        admin_role_id: String,
+        demote_previous_owner: bool,
+        replier: Sender<Result<(), Error>>,
+    },
+    Remove {
+        id: String,
+        replier: Sender<Result<(), Error>>,
+    },
 }
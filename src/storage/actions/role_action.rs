@@ -1,9 +1,42 @@
 use cp_microservice::core::error::Error;
 use tokio::sync::oneshot::Sender;
 
+use crate::storage::{role::Role, role_deletion::RoleDeletion, unique_write::UniqueWrite};
+
 #[derive(Debug)]
 pub enum RoleAction {
     GetAdminRoleId {
         replier: Sender<Result<String, Error>>,
     },
+    GetMemberRoleId {
+        replier: Sender<Result<String, Error>>,
+    },
+    GetByIds {
+        ids: Vec<String>,
+        replier: Sender<Result<Vec<Role>, Error>>,
+    },
+    Create {
+        organization_id: String,
+        name: String,
+        permissions: Vec<String>,
+        parents: Vec<String>,
+        replier: Sender<Result<UniqueWrite<String>, Error>>,
+    },
+    Update {
+        id: String,
+        organization_id: String,
+        name: Option<String>,
+        permissions: Option<Vec<String>>,
+        parents: Option<Vec<String>>,
+        replier: Sender<Result<UniqueWrite<bool>, Error>>,
+    },
+    Delete {
+        id: String,
+        organization_id: String,
+        replier: Sender<Result<RoleDeletion, Error>>,
+    },
+    ListByOrganization {
+        organization_id: String,
+        replier: Sender<Result<Vec<Role>, Error>>,
+    },
 }
@@ -0,0 +1,92 @@
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    options::UpdateOptions,
+    Client,
+};
+
+use crate::storage::{
+    storage_details::{DATABASE, ROLE_COLLECTION},
+    storage_error::is_duplicate_key_error,
+};
+
+const DEFAULT_ADMIN_ROLE_NAME: &str = "admin";
+const DEFAULT_MEMBER_ROLE_NAME: &str = "member";
+
+/// Creates the global `default_admin` and `default_member` roles unless they already exist and
+/// sets their permissions to the configured ones, so configuration changes apply on the next
+/// startup. Names of existing roles are left untouched.
+pub async fn seed_default_roles(
+    client: &Client,
+    admin_permissions: &[String],
+    member_permissions: &[String],
+) -> Result<(), Error> {
+    seed_default_role(
+        client,
+        "default_admin",
+        DEFAULT_ADMIN_ROLE_NAME,
+        admin_permissions,
+    )
+    .await?;
+    seed_default_role(
+        client,
+        "default_member",
+        DEFAULT_MEMBER_ROLE_NAME,
+        member_permissions,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn seed_default_role(
+    client: &Client,
+    flag: &str,
+    name: &str,
+    permissions: &[String],
+) -> Result<(), Error> {
+    let mut filter = Document::new();
+    filter.insert(flag, true);
+
+    let update = doc! {
+        "$set": {
+            "permissions": permissions
+        },
+        "$setOnInsert": {
+            "name": name
+        }
+    };
+
+    let roles = client
+        .database(DATABASE)
+        .collection::<Document>(ROLE_COLLECTION);
+
+    let result = match roles
+        .update_one(
+            filter.clone(),
+            update.clone(),
+            UpdateOptions::builder().upsert(true).build(),
+        )
+        .await
+    {
+        // The role flags are uniquely indexed, so an upsert racing with another instance seeding
+        // the same role fails with a duplicate key once that instance has created it. The role
+        // exists by then, so its permissions are updated without upserting.
+        Err(error) if is_duplicate_key_error(&error) => {
+            roles.update_one(filter, update, None).await
+        }
+        result => result,
+    };
+
+    if let Err(error) = result {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.default_roles.seed_default_role] failed to seed the '{}' role: {}",
+                name, &error
+            ),
+        ));
+    }
+
+    Ok(())
+}
@@ -9,8 +9,24 @@ use crate::storage::{
         organization_action::OrganizationAction, role_action::RoleAction,
     },
     executors::{
-        invitation_code_executor::create_invitation_code, member_executor::create_member,
-        organization_executor::create_organization, role_executor::get_admin_role_id,
+        invitation_code_executor::{
+            consume_invitation_code, create_invitation_code, list_organization_invitation_codes,
+            release_invitation_code, revoke_invitation_code,
+        },
+        member_executor::{
+            add_member_role, create_member, delete_member, get_member, join_member,
+            list_organization_members, list_user_organizations, remove_member_role,
+            update_member_permissions, update_member_roles,
+        },
+        organization_executor::{
+            create_organization, create_organization_with_admin, delete_organization,
+            get_organization, remove_organization, restore_organization, set_organization_owner,
+            transfer_organization_ownership, update_organization,
+        },
+        role_executor::{
+            create_role, delete_role, get_admin_role_id, get_member_role_id, get_roles_by_ids,
+            list_organization_roles, update_role,
+        },
     },
     storage_request::StorageRequest,
 };
@@ -41,13 +57,105 @@ impl Dispatch {
                 StorageRequest::Organization(action) => match action {
                     Some(action) => match action {
                         OrganizationAction::Create {
+                            id,
                             country,
                             name,
                             address,
+                            owner,
                             replier,
                         } => {
-                            create_organization(client.clone(), country, name, address, replier)
-                                .await;
+                            create_organization(
+                                client.clone(),
+                                id,
+                                country,
+                                name,
+                                address,
+                                owner,
+                                replier,
+                            )
+                            .await;
+                        }
+                        OrganizationAction::CreateWithAdmin {
+                            country,
+                            name,
+                            address,
+                            user_id,
+                            admin_role_id,
+                            replier,
+                        } => {
+                            create_organization_with_admin(
+                                client.clone(),
+                                country,
+                                name,
+                                address,
+                                user_id,
+                                admin_role_id,
+                                replier,
+                            )
+                            .await;
+                        }
+                        OrganizationAction::Get { id, replier } => {
+                            get_organization(client.clone(), id, replier).await;
+                        }
+                        OrganizationAction::Update {
+                            id,
+                            country,
+                            name,
+                            address,
+                            replier,
+                        } => {
+                            update_organization(
+                                client.clone(),
+                                id,
+                                country,
+                                name,
+                                address,
+                                replier,
+                            )
+                            .await;
+                        }
+                        OrganizationAction::Delete { id, replier } => {
+                            delete_organization(client.clone(), id, replier).await;
+                        }
+                        OrganizationAction::Restore { id, replier } => {
+                            restore_organization(client.clone(), id, replier).await;
+                        }
+                        OrganizationAction::SetOwner {
+                            id,
+                            previous_owner,
+                            owner,
+                            replier,
+                        } => {
+                            set_organization_owner(
+                                client.clone(),
+                                id,
+                                previous_owner,
+                                owner,
+                                replier,
+                            )
+                            .await;
+                        }
+                        OrganizationAction::TransferOwnership {
+                            id,
+                            previous_owner,
+                            new_owner,
+                            admin_role_id,
+                            demote_previous_owner,
+                            replier,
+                        } => {
+                            transfer_organization_ownership(
+                                client.clone(),
+                                id,
+                                previous_owner,
+                                new_owner,
+                                admin_role_id,
+                                demote_previous_owner,
+                                replier,
+                            )
+                            .await;
+                        }
+                        OrganizationAction::Remove { id, replier } => {
+                            remove_organization(client.clone(), id, replier).await;
                         }
                     },
                     None => {
@@ -59,6 +167,61 @@ impl Dispatch {
                         RoleAction::GetAdminRoleId { replier } => {
                             get_admin_role_id(client.clone(), replier).await;
                         }
+                        RoleAction::GetMemberRoleId { replier } => {
+                            get_member_role_id(client.clone(), replier).await;
+                        }
+                        RoleAction::GetByIds { ids, replier } => {
+                            get_roles_by_ids(client.clone(), ids, replier).await;
+                        }
+                        RoleAction::Create {
+                            organization_id,
+                            name,
+                            permissions,
+                            parents,
+                            replier,
+                        } => {
+                            create_role(
+                                client.clone(),
+                                organization_id,
+                                name,
+                                permissions,
+                                parents,
+                                replier,
+                            )
+                            .await;
+                        }
+                        RoleAction::Update {
+                            id,
+                            organization_id,
+                            name,
+                            permissions,
+                            parents,
+                            replier,
+                        } => {
+                            update_role(
+                                client.clone(),
+                                id,
+                                organization_id,
+                                name,
+                                permissions,
+                                parents,
+                                replier,
+                            )
+                            .await;
+                        }
+                        RoleAction::Delete {
+                            id,
+                            organization_id,
+                            replier,
+                        } => {
+                            delete_role(client.clone(), id, organization_id, replier).await;
+                        }
+                        RoleAction::ListByOrganization {
+                            organization_id,
+                            replier,
+                        } => {
+                            list_organization_roles(client.clone(), organization_id, replier).await;
+                        }
                     },
                     None => {
                         log::warn!("received empty role action");
@@ -81,6 +244,143 @@ impl Dispatch {
                             )
                             .await;
                         }
+                        MemberAction::Join {
+                            user_id,
+                            organization_id,
+                            roles,
+                            permissions,
+                            denied_permissions,
+                            replier,
+                        } => {
+                            join_member(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                roles,
+                                permissions,
+                                denied_permissions,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::Get {
+                            user_id,
+                            organization_id,
+                            replier,
+                        } => {
+                            get_member(client.clone(), user_id, organization_id, replier).await;
+                        }
+                        MemberAction::UpdateRoles {
+                            user_id,
+                            organization_id,
+                            change,
+                            admin_role_id,
+                            within_transaction,
+                            replier,
+                        } => {
+                            update_member_roles(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                change,
+                                admin_role_id,
+                                within_transaction,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::UpdatePermissions {
+                            user_id,
+                            organization_id,
+                            permissions,
+                            denied_permissions,
+                            admin_role_id,
+                            within_transaction,
+                            replier,
+                        } => {
+                            update_member_permissions(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                permissions,
+                                denied_permissions,
+                                admin_role_id,
+                                within_transaction,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::AddRole {
+                            user_id,
+                            organization_id,
+                            role_id,
+                            replier,
+                        } => {
+                            add_member_role(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                role_id,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::RemoveRole {
+                            user_id,
+                            organization_id,
+                            role_id,
+                            replier,
+                        } => {
+                            remove_member_role(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                role_id,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::Delete {
+                            user_id,
+                            organization_id,
+                            admin_role_id,
+                            within_transaction,
+                            replier,
+                        } => {
+                            delete_member(
+                                client.clone(),
+                                user_id,
+                                organization_id,
+                                admin_role_id,
+                                within_transaction,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::ListByOrganization {
+                            organization_id,
+                            role_id,
+                            sort_field,
+                            descending,
+                            cursor,
+                            limit,
+                            replier,
+                        } => {
+                            list_organization_members(
+                                client.clone(),
+                                organization_id,
+                                role_id,
+                                sort_field,
+                                descending,
+                                cursor,
+                                limit,
+                                replier,
+                            )
+                            .await;
+                        }
+                        MemberAction::ListUserOrganizations { user_id, replier } => {
+                            list_user_organizations(client.clone(), user_id, replier).await;
+                        }
                     },
                     None => {
                         log::warn!("received empty member action");
@@ -92,7 +392,10 @@ impl Dispatch {
                             code,
                             org_id,
                             permissions,
+                            denied_permissions,
                             roles,
+                            expires_at,
+                            max_uses,
                             replier,
                         } => {
                             create_invitation_code(
@@ -100,11 +403,48 @@ impl Dispatch {
                                 code,
                                 org_id,
                                 permissions,
+                                denied_permissions,
                                 roles,
+                                expires_at,
+                                max_uses,
+                                replier,
+                            )
+                            .await;
+                        }
+                        InvitationCodeAction::Consume {
+                            code,
+                            user_id,
+                            redemption_id,
+                            replier,
+                        } => {
+                            consume_invitation_code(
+                                client.clone(),
+                                code,
+                                user_id,
+                                redemption_id,
                                 replier,
                             )
                             .await;
                         }
+                        InvitationCodeAction::ListByOrganization { org_id, replier } => {
+                            list_organization_invitation_codes(client.clone(), org_id, replier)
+                                .await;
+                        }
+                        InvitationCodeAction::Revoke {
+                            org_id,
+                            code,
+                            replier,
+                        } => {
+                            revoke_invitation_code(client.clone(), org_id, code, replier).await;
+                        }
+                        InvitationCodeAction::Release {
+                            code,
+                            redemption_id,
+                            replier,
+                        } => {
+                            release_invitation_code(client.clone(), code, redemption_id, replier)
+                                .await;
+                        }
                     },
                     None => {
                         log::warn!("received empty invitation code action");
@@ -1,18 +1,26 @@
-use bson::doc;
+use bson::{doc, DateTime, Document};
 use cp_microservice::core::error::{Error, ErrorKind};
 use mongodb::Client;
 
-use crate::storage::storage_details::{DATABASE, INVITATION_CODE_COLLECTION};
+use crate::storage::{
+    invitation_code::InvitationCode,
+    storage_details::{DATABASE, INVITATION_CODE_COLLECTION},
+    storage_error::is_duplicate_key_error,
+    unique_write::UniqueWrite,
+};
 
 pub async fn create_invitation_code(
     client: Client,
     code: String,
     org_id: String,
     permissions: Vec<String>,
+    denied_permissions: Vec<String>,
     roles: Vec<String>,
-    replier: tokio::sync::oneshot::Sender<Result<String, Error>>,
+    expires_at: Option<DateTime>,
+    max_uses: Option<u32>,
+    replier: tokio::sync::oneshot::Sender<Result<UniqueWrite<String>, Error>>,
 ) -> Result<(), Error> {
-    match client
+    let code_write = match client
         .database(DATABASE)
         .collection(INVITATION_CODE_COLLECTION)
         .insert_one(
@@ -20,15 +28,26 @@ pub async fn create_invitation_code(
                 "code": code.clone(),
                 "org_id": org_id,
                 "permissions": permissions,
-                "roles": roles
+                "denied_permissions": denied_permissions,
+                "roles": roles,
+                "uses": 0i64,
+                "max_uses": max_uses.map(|max_uses| max_uses as i64),
+                "expires_at": expires_at
             },
             None,
         )
         .await
     {
-        Ok(_) => (),
+        Ok(_) => UniqueWrite::Written(code),
+        Err(error) if is_duplicate_key_error(&error) => UniqueWrite::Duplicate,
         Err(error) => {
-            let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.create_invitation_code] failed to insert invitation code: {}", &error));
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.invitation_code_executor.create_invitation_code] failed to insert invitation code: {}",
+                    &error
+                ),
+            );
 
             if let Err(_) = replier.send(Err(error.clone())) {
                 log::warn!("failed to reply to logic with an error");
@@ -38,9 +57,258 @@ pub async fn create_invitation_code(
         }
     };
 
-    if let Err(_) = replier.send(Ok(code)) {
+    if let Err(_) = replier.send(Ok(code_write)) {
         log::warn!("failed to reply to logic with an ok");
     }
 
     return Ok(());
 }
+
+pub async fn consume_invitation_code(
+    client: Client,
+    code: String,
+    user_id: String,
+    redemption_id: String,
+    replier: tokio::sync::oneshot::Sender<Result<Option<InvitationCode>, Error>>,
+) -> Result<(), Error> {
+    let invitation_code = match client
+        .database(DATABASE)
+        .collection::<InvitationCode>(INVITATION_CODE_COLLECTION)
+        .find_one_and_update(
+            doc! {
+                "code": &code,
+                "$and": [
+                    {
+                        "$or": [
+                            { "expires_at": null },
+                            { "expires_at": { "$gt": DateTime::now() } }
+                        ]
+                    },
+                    // An explicitly null limit means unlimited uses, whereas codes stored before
+                    // limits existed lack the field and remain single use.
+                    {
+                        "$or": [
+                            { "max_uses": { "$type": "null" } },
+                            {
+                                "$expr": {
+                                    "$lt": [
+                                        { "$ifNull": ["$uses", 0i64] },
+                                        { "$ifNull": ["$max_uses", 1i64] }
+                                    ]
+                                }
+                            }
+                        ]
+                    }
+                ]
+            },
+            doc! {
+                "$inc": {
+                    "uses": 1i64
+                },
+                "$push": {
+                    "redemptions": {
+                        "id": redemption_id,
+                        "user_id": user_id,
+                        "redeemed_at": DateTime::now()
+                    }
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(invitation_code) => invitation_code,
+        Err(error) => {
+            let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.consume_invitation_code] failed to consume invitation code: {}", &error));
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(invitation_code)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+/// Undoes a single redemption. A user may redeem the same code more than once (e.g. after
+/// leaving the organization), so the redemption is identified by its own id. Releasing a
+/// redemption which is not recorded, because it was already released or never consumed, leaves
+/// the code untouched.
+pub async fn release_invitation_code(
+    client: Client,
+    code: String,
+    redemption_id: String,
+    replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    match client
+        .database(DATABASE)
+        .collection::<Document>(INVITATION_CODE_COLLECTION)
+        .update_one(
+            release_filter(&code, &redemption_id),
+            doc! {
+                "$inc": {
+                    "uses": -1i64
+                },
+                "$pull": {
+                    "redemptions": {
+                        "id": &redemption_id
+                    }
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 0 => {
+            log::info!(
+                "redemption '{}' of invitation code '{}' is already released",
+                &redemption_id,
+                &code
+            );
+        }
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.release_invitation_code] failed to release invitation code: {}", &error));
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(())) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+/// Matches the code only while it still records the redemption, so it is released at most once.
+fn release_filter(code: &str, redemption_id: &str) -> Document {
+    doc! {
+        "code": code,
+        "redemptions.id": redemption_id
+    }
+}
+
+pub async fn list_organization_invitation_codes(
+    client: Client,
+    org_id: String,
+    replier: tokio::sync::oneshot::Sender<Result<Vec<InvitationCode>, Error>>,
+) -> Result<(), Error> {
+    let mut cursor = match client
+        .database(DATABASE)
+        .collection::<InvitationCode>(INVITATION_CODE_COLLECTION)
+        .find(
+            doc! {
+                "org_id": org_id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.list_organization_invitation_codes] failed to find invitation codes: {}", &error));
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut invitation_codes: Vec<InvitationCode> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.list_organization_invitation_codes] failed to advance cursor: {}", &error));
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(invitation_code) => invitation_codes.push(invitation_code),
+            Err(error) => {
+                let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.list_organization_invitation_codes] failed to deserialize invitation code: {}", &error));
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(invitation_codes)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn revoke_invitation_code(
+    client: Client,
+    org_id: String,
+    code: String,
+    replier: tokio::sync::oneshot::Sender<Result<bool, Error>>,
+) -> Result<(), Error> {
+    let found = match client
+        .database(DATABASE)
+        .collection::<Document>(INVITATION_CODE_COLLECTION)
+        .delete_one(
+            doc! {
+                "org_id": org_id,
+                "code": code
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.deleted_count > 0,
+        Err(error) => {
+            let error = Error::new(ErrorKind::StorageError, format!("[storage.invitation_code_executor.revoke_invitation_code] failed to delete invitation code: {}", &error));
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(found)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn release_filter_requires_recorded_redemption() {
+    assert_eq!(
+        doc! {
+            "code": "1234abcd",
+            "redemptions.id": "redemption"
+        },
+        release_filter("1234abcd", "redemption")
+    );
+}
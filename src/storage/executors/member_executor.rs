@@ -1,12 +1,21 @@
-use bson::doc;
+use bson::{doc, oid::ObjectId, Bson, Document};
 use cp_microservice::core::error::{Error, ErrorKind};
-use mongodb::Client;
+use mongodb::{
+    options::{FindOptions, UpdateModifications},
+    Client,
+};
 use tokio::sync::oneshot::Sender;
 
 use crate::storage::{
     actions::member_action::MemberAction,
-    storage_details::{DATABASE, MEMBER_COLLECTION},
+    member::Member,
+    member_page::{MemberPage, MemberSortField},
+    member_write::{MemberRolesChange, MemberWrite},
+    storage_details::{DATABASE, MEMBER_COLLECTION, ORGANIZATION_COLLECTION},
+    storage_error::{is_duplicate_key_error, write_error},
     storage_request::StorageRequest,
+    unique_write::UniqueWrite,
+    user_organization::UserOrganization,
 };
 
 pub async fn create_member(
@@ -23,18 +32,353 @@ pub async fn create_member(
             doc! {
                 "user_id": user_id,
                 "organization_id": organization_id,
-                "roles": vec![admin_role_id]
+                "roles": vec![admin_role_id],
+                "permissions": [],
+                "denied_permissions": []
             },
             None,
         )
         .await
     {
+        let error = write_error(
+            "storage.member_executor.create_member",
+            "failed to insert member",
+            &error,
+        );
+
+        if let Err(_) = replier.send(Err(error.clone())) {
+            log::warn!("failed to reply to logic with an error");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = replier.send(Ok(())) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn join_member(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    roles: Vec<String>,
+    permissions: Vec<String>,
+    denied_permissions: Vec<String>,
+    replier: Sender<Result<UniqueWrite<()>, Error>>,
+) -> Result<(), Error> {
+    let member_write = match client
+        .database(DATABASE)
+        .collection(MEMBER_COLLECTION)
+        .insert_one(
+            doc! {
+                "user_id": user_id,
+                "organization_id": organization_id,
+                "roles": roles,
+                "permissions": permissions,
+                "denied_permissions": denied_permissions
+            },
+            None,
+        )
+        .await
+    {
+        Ok(_) => UniqueWrite::Written(()),
+        Err(error) if is_duplicate_key_error(&error) => UniqueWrite::Duplicate,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.join_member] failed to insert member: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(member_write)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn get_member(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    replier: Sender<Result<Option<Member>, Error>>,
+) -> Result<(), Error> {
+    let member = match client
+        .database(DATABASE)
+        .collection::<Member>(MEMBER_COLLECTION)
+        .find_one(
+            doc! {
+                "user_id": user_id,
+                "organization_id": organization_id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(member) => member,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.get_member] failed to find member: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(member)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn update_member_roles(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    change: MemberRolesChange,
+    admin_role_id: String,
+    within_transaction: bool,
+    replier: Sender<Result<MemberWrite, Error>>,
+) -> Result<(), Error> {
+    let mut filter = doc! {
+        "user_id": user_id,
+        "organization_id": &organization_id
+    };
+
+    let update: UpdateModifications = match change {
+        MemberRolesChange::Replace { previous, roles } => {
+            filter.insert("roles", previous);
+
+            doc! {
+                "$set": {
+                    "roles": roles
+                }
+            }
+            .into()
+        }
+        MemberRolesChange::Change { add, remove } if remove.is_empty() => doc! {
+            "$addToSet": {
+                "roles": {
+                    "$each": add
+                }
+            }
+        }
+        .into(),
+        MemberRolesChange::Change { add, remove } if add.is_empty() => doc! {
+            "$pull": {
+                "roles": {
+                    "$in": remove
+                }
+            }
+        }
+        .into(),
+        // A single update cannot both $addToSet and $pull the same field, so the roles are
+        // recomputed by a pipeline instead, still within one atomic write.
+        MemberRolesChange::Change { add, remove } => vec![doc! {
+            "$set": {
+                "roles": {
+                    "$filter": {
+                        "input": {
+                            "$concatArrays": [
+                                "$roles",
+                                {
+                                    "$filter": {
+                                        "input": add,
+                                        "as": "role",
+                                        "cond": { "$not": [{ "$in": ["$$role", "$roles"] }] }
+                                    }
+                                }
+                            ]
+                        },
+                        "as": "role",
+                        "cond": { "$not": [{ "$in": ["$$role", remove] }] }
+                    }
+                }
+            }
+        }]
+        .into(),
+    };
+
+    let result = write_member_keeping_admin(
+        &client,
+        &organization_id,
+        &admin_role_id,
+        filter,
+        MemberChange::Update(update),
+        within_transaction,
+    )
+    .await;
+
+    match result {
+        Ok(member_write) => {
+            if let Err(_) = replier.send(Ok(member_write)) {
+                log::warn!("failed to reply to logic with an ok");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            Err(error)
+        }
+    }
+}
+
+pub async fn update_member_permissions(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    permissions: Option<Vec<String>>,
+    denied_permissions: Option<Vec<String>>,
+    admin_role_id: String,
+    within_transaction: bool,
+    replier: Sender<Result<MemberWrite, Error>>,
+) -> Result<(), Error> {
+    let mut update = Document::new();
+
+    if let Some(permissions) = permissions {
+        update.insert("permissions", permissions);
+    }
+
+    if let Some(denied_permissions) = denied_permissions {
+        update.insert("denied_permissions", denied_permissions);
+    }
+
+    let result = write_member_keeping_admin(
+        &client,
+        &organization_id,
+        &admin_role_id,
+        doc! {
+            "user_id": user_id,
+            "organization_id": &organization_id
+        },
+        MemberChange::Update(
+            doc! {
+                "$set": update
+            }
+            .into(),
+        ),
+        within_transaction,
+    )
+    .await;
+
+    match result {
+        Ok(member_write) => {
+            if let Err(_) = replier.send(Ok(member_write)) {
+                log::warn!("failed to reply to logic with an ok");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            Err(error)
+        }
+    }
+}
+
+pub async fn add_member_role(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    role_id: String,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    change_member_role(
+        client,
+        user_id,
+        organization_id,
+        doc! {
+            "$addToSet": {
+                "roles": role_id
+            }
+        },
+        "add_member_role",
+        replier,
+    )
+    .await
+}
+
+pub async fn remove_member_role(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    role_id: String,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    change_member_role(
+        client,
+        user_id,
+        organization_id,
+        doc! {
+            "$pull": {
+                "roles": role_id
+            }
+        },
+        "remove_member_role",
+        replier,
+    )
+    .await
+}
+
+async fn change_member_role(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    update: Document,
+    location: &str,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let message = match client
+        .database(DATABASE)
+        .collection::<Document>(MEMBER_COLLECTION)
+        .update_one(
+            doc! {
+                "user_id": user_id,
+                "organization_id": organization_id
+            },
+            update,
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count > 0 => None,
+        Ok(_) => Some("member not found".to_string()),
+        Err(error) => Some(format!("failed to update member: {}", &error)),
+    };
+
+    if let Some(message) = message {
         let error = Error::new(
             ErrorKind::StorageError,
-            format!(
-                "[storage.member_executor.create_member] failed to insert member: {}",
-                &error
-            ),
+            format!("[storage.member_executor.{}] {}", location, message),
         );
 
         if let Err(_) = replier.send(Err(error.clone())) {
@@ -50,3 +394,622 @@ pub async fn create_member(
 
     Ok(())
 }
+
+pub async fn delete_member(
+    client: Client,
+    user_id: String,
+    organization_id: String,
+    admin_role_id: String,
+    within_transaction: bool,
+    replier: Sender<Result<MemberWrite, Error>>,
+) -> Result<(), Error> {
+    let result = write_member_keeping_admin(
+        &client,
+        &organization_id,
+        &admin_role_id,
+        doc! {
+            "user_id": user_id,
+            "organization_id": &organization_id
+        },
+        MemberChange::Delete,
+        within_transaction,
+    )
+    .await;
+
+    match result {
+        Ok(member_write) => {
+            if let Err(_) = replier.send(Ok(member_write)) {
+                log::warn!("failed to reply to logic with an ok");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            Err(error)
+        }
+    }
+}
+
+/// Change applied to a single member by `write_member_keeping_admin`.
+enum MemberChange {
+    Delete,
+    Update(UpdateModifications),
+}
+
+/// Applies `change` to the member matched by `filter` unless it leaves the organization without
+/// an unrestricted admin, that is a holder of the admin role who is denied no permission.
+/// Counting the admins before writing would let concurrent writes each see another admin and
+/// remove them all, so the count is taken after the write instead.
+async fn write_member_keeping_admin(
+    client: &Client,
+    organization_id: &str,
+    admin_role_id: &str,
+    filter: Document,
+    change: MemberChange,
+    within_transaction: bool,
+) -> Result<MemberWrite, Error> {
+    if within_transaction {
+        write_member_within_transaction(client, organization_id, admin_role_id, filter, change)
+            .await
+    } else {
+        write_member_and_recheck(client, organization_id, admin_role_id, filter, change).await
+    }
+}
+
+/// Writes the member, then counts the remaining admins and restores the member's previous state
+/// when none is left. Concurrent writers restore their own member, so the organization keeps at
+/// least one admin once every write has settled.
+async fn write_member_and_recheck(
+    client: &Client,
+    organization_id: &str,
+    admin_role_id: &str,
+    filter: Document,
+    change: MemberChange,
+) -> Result<MemberWrite, Error> {
+    let collection = client
+        .database(DATABASE)
+        .collection::<Document>(MEMBER_COLLECTION);
+
+    let previous = match &change {
+        MemberChange::Delete => collection.find_one_and_delete(filter, None).await,
+        MemberChange::Update(update) => {
+            collection
+                .find_one_and_update(filter, update.clone(), None)
+                .await
+        }
+    };
+
+    let previous = match previous {
+        Ok(Some(previous)) => previous,
+        Ok(None) => return Ok(MemberWrite::NotFound),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.write_member_and_recheck] failed to write member: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    if !is_unrestricted_admin(&previous, admin_role_id) {
+        return Ok(MemberWrite::Applied);
+    }
+
+    let admins = match collection
+        .count_documents(
+            unrestricted_admins_filter(organization_id, admin_role_id),
+            None,
+        )
+        .await
+    {
+        Ok(admins) => admins,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.write_member_and_recheck] failed to count admins: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    if admins > 0u64 {
+        return Ok(MemberWrite::Applied);
+    }
+
+    let restore = match change {
+        MemberChange::Delete => collection.insert_one(&previous, None).await.map(|_| ()),
+        MemberChange::Update(_) => collection
+            .update_one(
+                doc! {
+                    "_id": previous.get("_id").cloned()
+                },
+                doc! {
+                    "$set": {
+                        "roles": previous.get("roles").cloned(),
+                        "permissions": previous.get("permissions").cloned().unwrap_or(Bson::Array(Vec::new())),
+                        "denied_permissions": previous.get("denied_permissions").cloned().unwrap_or(Bson::Array(Vec::new()))
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ()),
+    };
+
+    if let Err(error) = restore {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.member_executor.write_member_and_recheck] failed to restore last admin: {}",
+                &error
+            ),
+        ));
+    }
+
+    Ok(MemberWrite::LastAdmin)
+}
+
+/// Writes the member and counts the remaining admins within a single transaction, aborting it
+/// when none is left. Every such transaction also bumps the organization's `membership_version`
+/// so that two of them removing different admins conflict instead of both committing.
+async fn write_member_within_transaction(
+    client: &Client,
+    organization_id: &str,
+    admin_role_id: &str,
+    filter: Document,
+    change: MemberChange,
+) -> Result<MemberWrite, Error> {
+    let organization_object_id = match ObjectId::parse_str(organization_id) {
+        Ok(organization_object_id) => organization_object_id,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.write_member_within_transaction] invalid organization id: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    let mut session = match client.start_session(None).await {
+        Ok(session) => session,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.write_member_within_transaction] failed to start session: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    if let Err(error) = session.start_transaction(None).await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.member_executor.write_member_within_transaction] failed to start transaction: {}",
+                &error
+            ),
+        ));
+    }
+
+    let database = client.database(DATABASE);
+    let collection = database.collection::<Document>(MEMBER_COLLECTION);
+
+    let outcome = match database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one_with_session(
+            doc! {
+                "_id": organization_object_id
+            },
+            doc! {
+                "$inc": {
+                    "membership_version": 1i64
+                }
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!(
+            "failed to lock organization membership: {}",
+            &error
+        )),
+    };
+
+    let outcome = match outcome {
+        Ok(()) => match change {
+            MemberChange::Delete => {
+                collection
+                    .find_one_and_delete_with_session(filter, None, &mut session)
+                    .await
+            }
+            MemberChange::Update(update) => {
+                collection
+                    .find_one_and_update_with_session(filter, update, None, &mut session)
+                    .await
+            }
+        }
+        .map_err(|error| format!("failed to write member: {}", &error)),
+        Err(message) => Err(message),
+    };
+
+    let outcome = match outcome {
+        Ok(Some(previous)) if is_unrestricted_admin(&previous, admin_role_id) => match collection
+            .count_documents_with_session(
+                unrestricted_admins_filter(organization_id, admin_role_id),
+                None,
+                &mut session,
+            )
+            .await
+        {
+            Ok(admins) if admins > 0u64 => Ok(MemberWrite::Applied),
+            Ok(_) => Ok(MemberWrite::LastAdmin),
+            Err(error) => Err(format!("failed to count admins: {}", &error)),
+        },
+        Ok(Some(_)) => Ok(MemberWrite::Applied),
+        Ok(None) => Ok(MemberWrite::NotFound),
+        Err(message) => Err(message),
+    };
+
+    match outcome {
+        Ok(MemberWrite::Applied) => {
+            if let Err(error) = session.commit_transaction().await {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.write_member_within_transaction] failed to commit transaction: {}",
+                        &error
+                    ),
+                ));
+            }
+
+            Ok(MemberWrite::Applied)
+        }
+        Ok(member_write) => {
+            if let Err(error) = session.abort_transaction().await {
+                log::warn!("failed to abort member write transaction: {}", &error);
+            }
+
+            Ok(member_write)
+        }
+        Err(message) => {
+            if let Err(error) = session.abort_transaction().await {
+                log::warn!("failed to abort member write transaction: {}", &error);
+            }
+
+            Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.write_member_within_transaction] {}",
+                    message
+                ),
+            ))
+        }
+    }
+}
+
+fn is_unrestricted_admin(member: &Document, admin_role_id: &str) -> bool {
+    let holds_admin_role = member.get_array("roles").is_ok_and(|roles| {
+        roles
+            .iter()
+            .any(|role| role.as_str().is_some_and(|role| role == admin_role_id))
+    });
+
+    let is_denied_permissions = member
+        .get_array("denied_permissions")
+        .is_ok_and(|denied_permissions| !denied_permissions.is_empty());
+
+    holds_admin_role && !is_denied_permissions
+}
+
+fn unrestricted_admins_filter(organization_id: &str, admin_role_id: &str) -> Document {
+    doc! {
+        "organization_id": organization_id,
+        "roles": admin_role_id,
+        "denied_permissions.0": {
+            "$exists": false
+        }
+    }
+}
+
+pub async fn list_organization_members(
+    client: Client,
+    organization_id: String,
+    role_id: Option<String>,
+    sort_field: MemberSortField,
+    descending: bool,
+    cursor: Option<String>,
+    limit: u32,
+    replier: Sender<Result<MemberPage, Error>>,
+) -> Result<(), Error> {
+    let mut filter = doc! {
+        "organization_id": organization_id
+    };
+
+    if let Some(role_id) = role_id {
+        filter.insert("roles", role_id);
+    }
+
+    if let Some(cursor) = cursor {
+        match sort_field.cursor_filter(&cursor, descending) {
+            Ok(cursor_filter) => filter.extend(cursor_filter),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.list_organization_members] {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    let mut sort = Document::new();
+    sort.insert(sort_field.key(), if descending { -1 } else { 1 });
+
+    // One more member than requested is fetched to know whether there is a next page.
+    let find_options = FindOptions::builder()
+        .sort(sort)
+        .limit(limit as i64 + 1)
+        .build();
+
+    let mut cursor = match client
+        .database(DATABASE)
+        .collection::<Member>(MEMBER_COLLECTION)
+        .find(filter, find_options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.list_organization_members] failed to find members: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut members: Vec<Member> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.list_organization_members] failed to advance cursor: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(member) => members.push(member),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.list_organization_members] failed to deserialize member: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(MemberPage::from_fetched(members, sort_field, limit))) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn list_user_organizations(
+    client: Client,
+    user_id: String,
+    replier: Sender<Result<Vec<UserOrganization>, Error>>,
+) -> Result<(), Error> {
+    let pipeline = user_organizations_pipeline(user_id);
+
+    let mut cursor = match client
+        .database(DATABASE)
+        .collection::<Document>(MEMBER_COLLECTION)
+        .aggregate(pipeline, None)
+        .await
+    {
+        Ok(cursor) => cursor.with_type::<UserOrganization>(),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.member_executor.list_user_organizations] failed to aggregate user organizations: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut user_organizations: Vec<UserOrganization> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.list_user_organizations] failed to advance cursor: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(user_organization) => user_organizations.push(user_organization),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.member_executor.list_user_organizations] failed to deserialize user organization: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(user_organizations)) {
+        log::warn!("failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+/// Joins the memberships of the user with their organizations, skipping deleted ones. Members
+/// whose organization id is not a valid object id match no organization instead of failing the
+/// whole aggregation.
+fn user_organizations_pipeline(user_id: String) -> Vec<Document> {
+    vec![
+        doc! {
+            "$match": {
+                "user_id": user_id
+            }
+        },
+        doc! {
+            "$lookup": {
+                "from": ORGANIZATION_COLLECTION,
+                "let": {
+                    "organization_id": {
+                        "$convert": {
+                            "input": "$organization_id",
+                            "to": "objectId",
+                            "onError": null,
+                            "onNull": null
+                        }
+                    }
+                },
+                "pipeline": [
+                    {
+                        "$match": {
+                            "$expr": {
+                                "$eq": ["$_id", "$$organization_id"]
+                            },
+                            "deleted_at": null
+                        }
+                    }
+                ],
+                "as": "organization"
+            }
+        },
+        doc! {
+            "$unwind": "$organization"
+        },
+        doc! {
+            "$project": {
+                "_id": 0,
+                "organization": 1,
+                "roles": 1
+            }
+        },
+    ]
+}
+
+#[test]
+pub fn user_organizations_pipeline_matches_memberships_of_user() {
+    let pipeline = user_organizations_pipeline("gabriel".to_string());
+
+    assert_eq!(
+        doc! {
+            "$match": {
+                "user_id": "gabriel"
+            }
+        },
+        pipeline[0]
+    );
+}
+
+#[test]
+pub fn user_organizations_pipeline_tolerates_malformed_organization_ids() {
+    let pipeline = user_organizations_pipeline("gabriel".to_string());
+
+    let organization_id = pipeline[1]
+        .get_document("$lookup")
+        .and_then(|lookup| lookup.get_document("let"))
+        .and_then(|variables| variables.get_document("organization_id"))
+        .unwrap();
+
+    assert_eq!(
+        &doc! {
+            "$convert": {
+                "input": "$organization_id",
+                "to": "objectId",
+                "onError": null,
+                "onNull": null
+            }
+        },
+        organization_id
+    );
+}
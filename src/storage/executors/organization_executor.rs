@@ -1,24 +1,32 @@
 use cp_core::geolocalization::address::Address;
 use cp_microservice::core::error::{Error, ErrorKind};
 use mongodb::{
-    bson::{bson, doc},
+    bson::{bson, doc, oid::ObjectId, Bson, DateTime, Document},
     Client,
 };
 use tokio::sync::oneshot::Sender;
 
 use crate::storage::{
     actions::organization_action::OrganizationAction,
-    storage_details::{DATABASE, ORGANIZATION_COLLECTION},
+    organization::Organization,
+    storage_details::{DATABASE, MEMBER_COLLECTION, ORGANIZATION_COLLECTION},
+    storage_error::write_error,
     storage_request::StorageRequest,
 };
 
+/// Inserts the organization under the id chosen by the caller, so that the caller knows which
+/// organization to remove even when the reply to the insert is lost.
 pub async fn create_organization(
     client: Client,
+    id: String,
     country: String,
     name: String,
     address: Address,
+    owner: String,
     replier: Sender<Result<String, Error>>,
 ) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
     let address_bson = match bson::to_bson(&address) {
         Ok(address_bson) => address_bson,
         Err(error) => {
@@ -29,45 +37,302 @@ pub async fn create_organization(
         }
     };
 
-    let organization_id = match client
+    if let Err(error) = client
         .database(DATABASE)
         .collection(ORGANIZATION_COLLECTION)
         .insert_one(
             doc! {
+                "_id": object_id,
                 "country": &country,
                 "name": &name,
-                "address": address_bson
+                "address": address_bson,
+                "owner": owner
             },
             None,
         )
         .await
+    {
+        let error = Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.handle_create_organization] failed to insert new organization: {}", &error),
+        );
+
+        if let Err(_) = replier.send(Err(error.clone())) {
+            log::warn!("storage failed to reply with create organization related error to logic");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = replier.send(Ok(id)) {
+        log::warn!("storage failed to reply with organization id to logic");
+    }
+
+    Ok(())
+}
+
+/// Inserts the organization and its admin member within a single transaction, so a failure
+/// on either insert leaves no organization behind.
+pub async fn create_organization_with_admin(
+    client: Client,
+    country: String,
+    name: String,
+    address: Address,
+    user_id: String,
+    admin_role_id: String,
+    replier: Sender<Result<String, Error>>,
+) -> Result<(), Error> {
+    match insert_organization_with_admin(&client, country, name, address, user_id, admin_role_id)
+        .await
+    {
+        Ok(organization_id) => {
+            if let Err(_) = replier.send(Ok(organization_id)) {
+                log::warn!("storage failed to reply with organization id to logic");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage failed to reply with create organization with admin related error to logic"
+                );
+            }
+
+            Err(error)
+        }
+    }
+}
+
+async fn insert_organization_with_admin(
+    client: &Client,
+    country: String,
+    name: String,
+    address: Address,
+    user_id: String,
+    admin_role_id: String,
+) -> Result<String, Error> {
+    let address_bson = match bson::to_bson(&address) {
+        Ok(address_bson) => address_bson,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.create_organization_with_admin] failed to serialize 'address': {}", error),
+            ))
+        }
+    };
+
+    let mut session = match client.start_session(None).await {
+        Ok(session) => session,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.create_organization_with_admin] failed to start session: {}", &error),
+            ))
+        }
+    };
+
+    if let Err(error) = session.start_transaction(None).await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.create_organization_with_admin] failed to start transaction: {}", &error),
+        ));
+    }
+
+    let database = client.database(DATABASE);
+
+    let organization_id = match database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .insert_one_with_session(
+            doc! {
+                "country": &country,
+                "name": &name,
+                "address": address_bson,
+                "owner": &user_id
+            },
+            None,
+            &mut session,
+        )
+        .await
     {
         Ok(result) => match result.inserted_id.as_object_id() {
             Some(organization_id) => organization_id.to_string(),
             None => {
+                if let Err(error) = session.abort_transaction().await {
+                    log::warn!(
+                        "failed to abort create organization transaction: {}",
+                        &error
+                    );
+                }
+
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    "[storage.organization_executor.create_organization_with_admin] failed to get organization id from entry",
+                ));
+            }
+        },
+        Err(error) => {
+            if let Err(error) = session.abort_transaction().await {
+                log::warn!(
+                    "failed to abort create organization transaction: {}",
+                    &error
+                );
+            }
+
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.create_organization_with_admin] failed to insert new organization: {}", &error),
+            ));
+        }
+    };
+
+    if let Err(error) = database
+        .collection::<Document>(MEMBER_COLLECTION)
+        .insert_one_with_session(
+            doc! {
+                "user_id": user_id,
+                "organization_id": &organization_id,
+                "roles": vec![admin_role_id],
+                "permissions": [],
+                "denied_permissions": []
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        if let Err(error) = session.abort_transaction().await {
+            log::warn!(
+                "failed to abort create organization transaction: {}",
+                &error
+            );
+        }
+
+        return Err(write_error(
+            "storage.organization_executor.create_organization_with_admin",
+            "failed to insert admin member",
+            &error,
+        ));
+    }
+
+    if let Err(error) = session.commit_transaction().await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.create_organization_with_admin] failed to commit transaction: {}", &error),
+        ));
+    }
+
+    Ok(organization_id)
+}
+
+pub async fn get_organization(
+    client: Client,
+    id: String,
+    replier: Sender<Result<Option<Organization>, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let organization = match client
+        .database(DATABASE)
+        .collection::<Organization>(ORGANIZATION_COLLECTION)
+        .find_one(
+            Some(doc! {
+                "_id": object_id,
+                "deleted_at": null
+            }),
+            None,
+        )
+        .await
+    {
+        Ok(organization) => organization,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.get_organization] failed to find organization: {}", &error),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage failed to reply with get organization related error to logic");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(organization)) {
+        log::warn!("storage failed to reply with organization to logic");
+    }
+
+    Ok(())
+}
+
+pub async fn update_organization(
+    client: Client,
+    id: String,
+    country: Option<String>,
+    name: Option<String>,
+    address: Option<Address>,
+    replier: Sender<Result<bool, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let mut update = Document::new();
+
+    if let Some(country) = country {
+        update.insert("country", country);
+    }
+
+    if let Some(name) = name {
+        update.insert("name", name);
+    }
+
+    if let Some(address) = address {
+        let address_bson = match bson::to_bson(&address) {
+            Ok(address_bson) => address_bson,
+            Err(error) => {
                 let error = Error::new(
                     ErrorKind::StorageError,
-                    format!("[storage.organization_executor.handle_create_organization] failed to get organization id from entry"),
+                    format!("[storage.organization_executor.update_organization] failed to serialize 'address': {}", error),
                 );
 
                 if let Err(_) = replier.send(Err(error.clone())) {
                     log::warn!(
-                        "storage failed to reply with create organization id related error to logic"
+                        "storage failed to reply with update organization related error to logic"
                     );
                 }
 
                 return Err(error);
             }
-        },
+        };
+
+        update.insert("address", address_bson);
+    }
+
+    let found = match client
+        .database(DATABASE)
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one(
+            doc! {
+                "_id": object_id,
+                "deleted_at": null
+            },
+            doc! {
+                "$set": update
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.matched_count > 0,
         Err(error) => {
             let error = Error::new(
                 ErrorKind::StorageError,
-                format!("[storage.organization_executor.handle_create_organization] failed to insert new organization: {}", &error),
+                format!("[storage.organization_executor.update_organization] failed to update organization: {}", &error),
             );
 
             if let Err(_) = replier.send(Err(error.clone())) {
                 log::warn!(
-                    "storage failed to reply with create organization related error to logic"
+                    "storage failed to reply with update organization related error to logic"
                 );
             }
 
@@ -75,9 +340,459 @@ pub async fn create_organization(
         }
     };
 
-    if let Err(_) = replier.send(Ok(organization_id)) {
-        log::warn!("storage failed to reply with organization id to logic");
+    if let Err(_) = replier.send(Ok(found)) {
+        log::warn!("storage failed to reply with update organization result to logic");
     }
 
     Ok(())
 }
+
+pub async fn delete_organization(
+    client: Client,
+    id: String,
+    replier: Sender<Result<bool, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let found = match client
+        .database(DATABASE)
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one(
+            doc! {
+                "_id": object_id,
+                "deleted_at": null
+            },
+            doc! {
+                "$set": {
+                    "deleted_at": DateTime::now()
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.matched_count > 0,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.delete_organization] failed to mark organization as deleted: {}", &error),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage failed to reply with delete organization related error to logic"
+                );
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(found)) {
+        log::warn!("storage failed to reply with delete organization result to logic");
+    }
+
+    Ok(())
+}
+
+pub async fn restore_organization(
+    client: Client,
+    id: String,
+    replier: Sender<Result<bool, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let found = match client
+        .database(DATABASE)
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one(
+            // Organizations being purged have already lost part of their children.
+            doc! {
+                "_id": object_id,
+                "deleted_at": {
+                    "$ne": null
+                },
+                "purging": {
+                    "$ne": true
+                }
+            },
+            doc! {
+                "$unset": {
+                    "deleted_at": ""
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.matched_count > 0,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.restore_organization] failed to restore organization: {}", &error),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage failed to reply with restore organization related error to logic"
+                );
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(found)) {
+        log::warn!("storage failed to reply with restore organization result to logic");
+    }
+
+    Ok(())
+}
+
+/// Hands the organization over to `owner` as long as it is still owned by `previous_owner`, so
+/// concurrent transfers can not overwrite each other. Handing it over to its current owner
+/// succeeds, so the hand over may be repeated.
+pub async fn set_organization_owner(
+    client: Client,
+    id: String,
+    previous_owner: String,
+    owner: String,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let found = match client
+        .database(DATABASE)
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one(
+            doc! {
+                "_id": object_id,
+                "$or": [
+                    { "owner": owner_filter(&previous_owner) },
+                    { "owner": &owner }
+                ],
+                "deleted_at": null
+            },
+            doc! {
+                "$set": {
+                    "owner": &owner
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => result.matched_count > 0,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.set_organization_owner] failed to update organization owner: {}", &error),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage failed to reply with set organization owner related error to logic"
+                );
+            }
+
+            return Err(error);
+        }
+    };
+
+    if !found {
+        let error = Error::new(
+            ErrorKind::StorageError,
+            "[storage.organization_executor.set_organization_owner] organization not found or its owner changed",
+        );
+
+        if let Err(_) = replier.send(Err(error.clone())) {
+            log::warn!(
+                "storage failed to reply with set organization owner related error to logic"
+            );
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = replier.send(Ok(())) {
+        log::warn!("storage failed to reply with set organization owner result to logic");
+    }
+
+    Ok(())
+}
+
+/// Moves the ownership and grants the admin role to the new owner within a single
+/// transaction, optionally revoking the admin role of the previous owner as well.
+pub async fn transfer_organization_ownership(
+    client: Client,
+    id: String,
+    previous_owner: String,
+    new_owner: String,
+    admin_role_id: String,
+    demote_previous_owner: bool,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    match move_ownership_within_transaction(
+        &client,
+        object_id,
+        id,
+        previous_owner,
+        new_owner,
+        admin_role_id,
+        demote_previous_owner,
+    )
+    .await
+    {
+        Ok(()) => {
+            if let Err(_) = replier.send(Ok(())) {
+                log::warn!("storage failed to reply with transfer ownership result to logic");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage failed to reply with transfer ownership related error to logic"
+                );
+            }
+
+            Err(error)
+        }
+    }
+}
+
+async fn move_ownership_within_transaction(
+    client: &Client,
+    object_id: ObjectId,
+    id: String,
+    previous_owner: String,
+    new_owner: String,
+    admin_role_id: String,
+    demote_previous_owner: bool,
+) -> Result<(), Error> {
+    let mut session = match client.start_session(None).await {
+        Ok(session) => session,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_executor.move_ownership_within_transaction] failed to start session: {}", &error),
+            ))
+        }
+    };
+
+    if let Err(error) = session.start_transaction(None).await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.move_ownership_within_transaction] failed to start transaction: {}", &error),
+        ));
+    }
+
+    let database = client.database(DATABASE);
+
+    let message = match database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one_with_session(
+            doc! {
+                "_id": object_id,
+                "owner": owner_filter(&previous_owner),
+                "deleted_at": null
+            },
+            doc! {
+                "$set": {
+                    "owner": &new_owner
+                }
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count > 0 => None,
+        Ok(_) => Some("organization not found or its owner changed".to_string()),
+        Err(error) => Some(format!("failed to update organization owner: {}", &error)),
+    };
+
+    let message = match message {
+        Some(message) => Some(message),
+        None => match database
+            .collection::<Document>(MEMBER_COLLECTION)
+            .update_one_with_session(
+                doc! {
+                    "user_id": &new_owner,
+                    "organization_id": &id
+                },
+                doc! {
+                    "$addToSet": {
+                        "roles": &admin_role_id
+                    }
+                },
+                None,
+                &mut session,
+            )
+            .await
+        {
+            Ok(result) if result.matched_count > 0 => None,
+            Ok(_) => Some("new owner is not a member of the organization".to_string()),
+            Err(error) => Some(format!(
+                "failed to grant admin role to new owner: {}",
+                &error
+            )),
+        },
+    };
+
+    let message = match message {
+        Some(message) => Some(message),
+        None if demote_previous_owner => match database
+            .collection::<Document>(MEMBER_COLLECTION)
+            .update_one_with_session(
+                doc! {
+                    "user_id": &previous_owner,
+                    "organization_id": &id
+                },
+                doc! {
+                    "$pull": {
+                        "roles": &admin_role_id
+                    }
+                },
+                None,
+                &mut session,
+            )
+            .await
+        {
+            Ok(_) => None,
+            Err(error) => Some(format!(
+                "failed to revoke admin role from previous owner: {}",
+                &error
+            )),
+        },
+        None => None,
+    };
+
+    if let Some(message) = message {
+        if let Err(error) = session.abort_transaction().await {
+            log::warn!("failed to abort transfer ownership transaction: {}", &error);
+        }
+
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.organization_executor.move_ownership_within_transaction] {}",
+                message
+            ),
+        ));
+    }
+
+    if let Err(error) = session.commit_transaction().await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.move_ownership_within_transaction] failed to commit transaction: {}", &error),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Removes the organization along with its members, undoing its creation. Removing an
+/// organization which was never created has no effect.
+pub async fn remove_organization(
+    client: Client,
+    id: String,
+    replier: Sender<Result<(), Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_organization_id(&id, replier)?;
+
+    let database = client.database(DATABASE);
+
+    let removed = match database
+        .collection::<Document>(MEMBER_COLLECTION)
+        .delete_many(
+            doc! {
+                "organization_id": &id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(_) => {
+            database
+                .collection::<Document>(ORGANIZATION_COLLECTION)
+                .delete_one(
+                    doc! {
+                        "_id": object_id
+                    },
+                    None,
+                )
+                .await
+        }
+        Err(error) => Err(error),
+    };
+
+    if let Err(error) = removed {
+        let error = Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_executor.remove_organization] failed to remove organization: {}", &error),
+        );
+
+        if let Err(_) = replier.send(Err(error.clone())) {
+            log::warn!("storage failed to reply with remove organization related error to logic");
+        }
+
+        return Err(error);
+    }
+
+    if let Err(_) = replier.send(Ok(())) {
+        log::warn!("storage failed to reply with remove organization result to logic");
+    }
+
+    Ok(())
+}
+
+/// Matches the given owner. Organizations created before owners were tracked are read with an
+/// empty owner while their `owner` field may be missing, so an empty owner matches both.
+fn owner_filter(owner: &str) -> Bson {
+    if owner.is_empty() {
+        Bson::Document(doc! {
+            "$in": ["", null]
+        })
+    } else {
+        Bson::String(owner.to_string())
+    }
+}
+
+fn parse_organization_id<T>(
+    id: &str,
+    replier: Sender<Result<T, Error>>,
+) -> Result<(ObjectId, Sender<Result<T, Error>>), Error> {
+    match ObjectId::parse_str(id) {
+        Ok(object_id) => Ok((object_id, replier)),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.organization_executor.parse_organization_id] invalid organization id '{}': {}",
+                    id, &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage failed to reply with invalid organization id error to logic");
+            }
+
+            Err(error)
+        }
+    }
+}
+
+#[test]
+pub fn empty_owner_matches_missing_owner() {
+    assert_eq!(
+        Bson::Document(doc! {
+            "$in": ["", null]
+        }),
+        owner_filter("")
+    );
+    assert_eq!(Bson::String("gabriel".to_string()), owner_filter("gabriel"));
+}
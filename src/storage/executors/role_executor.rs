@@ -1,13 +1,19 @@
 use cp_microservice::core::error::{Error, ErrorKind};
-use mongodb::{bson::doc, Client};
+use mongodb::{
+    bson::{doc, oid::ObjectId, Document},
+    Client,
+};
 
 use tokio::sync::oneshot::Sender;
 
 use crate::storage::{
     actions::role_action::RoleAction,
     role::Role,
-    storage_details::{DATABASE, ROLE_COLLECTION},
+    role_deletion::RoleDeletion,
+    storage_details::{DATABASE, INVITATION_CODE_COLLECTION, MEMBER_COLLECTION, ROLE_COLLECTION},
+    storage_error::is_duplicate_key_error,
     storage_request::StorageRequest,
+    unique_write::UniqueWrite,
 };
 
 pub async fn get_admin_role_id(
@@ -64,3 +70,527 @@ pub async fn get_admin_role_id(
 
     Ok(())
 }
+
+pub async fn get_member_role_id(
+    client: Client,
+    replier: Sender<Result<String, Error>>,
+) -> Result<(), Error> {
+    let role = match client
+        .database(DATABASE)
+        .collection::<Role>(ROLE_COLLECTION)
+        .find_one(
+            Some(doc! {
+                "default_member": true
+            }),
+            None,
+        )
+        .await
+    {
+        Ok(role) => match role {
+            Some(role) => role,
+            None => {
+                let error = Error::new(
+                        ErrorKind::StorageError,
+                        "[storage.role_executor.handle_get_member_role_id] could not find the member role",
+                    );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("storage, handle_get_member_role_id, failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        },
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.role_executor.handle_get_member_role_id] failed to find the member role: {}", &error),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage, handle_get_member_role_id, failed to reply to logic with an error"
+                );
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(role.id().to_string())) {
+        log::warn!("storage, handle_get_member_role_id, failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn get_roles_by_ids(
+    client: Client,
+    ids: Vec<String>,
+    replier: Sender<Result<Vec<Role>, Error>>,
+) -> Result<(), Error> {
+    let object_ids: Vec<ObjectId> = ids
+        .iter()
+        .filter_map(|id| ObjectId::parse_str(id).ok())
+        .collect();
+
+    let mut cursor = match client
+        .database(DATABASE)
+        .collection::<Role>(ROLE_COLLECTION)
+        .find(
+            doc! {
+                "_id": {
+                    "$in": object_ids
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.get_roles_by_ids] failed to find roles: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage, get_roles_by_ids, failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut roles: Vec<Role> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.get_roles_by_ids] failed to advance cursor: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("storage, get_roles_by_ids, failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(role) => roles.push(role),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.get_roles_by_ids] failed to deserialize role: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("storage, get_roles_by_ids, failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(roles)) {
+        log::warn!("storage, get_roles_by_ids, failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn create_role(
+    client: Client,
+    organization_id: String,
+    name: String,
+    permissions: Vec<String>,
+    parents: Vec<String>,
+    replier: Sender<Result<UniqueWrite<String>, Error>>,
+) -> Result<(), Error> {
+    let role_write = match client
+        .database(DATABASE)
+        .collection::<Document>(ROLE_COLLECTION)
+        .insert_one(
+            doc! {
+                "name": name,
+                "permissions": permissions,
+                "parents": parents,
+                "organization_id": organization_id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => match result.inserted_id.as_object_id() {
+            Some(role_id) => UniqueWrite::Written(role_id.to_string()),
+            None => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    "[storage.role_executor.create_role] failed to get role id from entry",
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!("storage, create_role, failed to reply to logic with an error");
+                }
+
+                return Err(error);
+            }
+        },
+        Err(error) if is_duplicate_key_error(&error) => UniqueWrite::Duplicate,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.create_role] failed to insert role: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage, create_role, failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(role_write)) {
+        log::warn!("storage, create_role, failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+pub async fn update_role(
+    client: Client,
+    id: String,
+    organization_id: String,
+    name: Option<String>,
+    permissions: Option<Vec<String>>,
+    parents: Option<Vec<String>>,
+    replier: Sender<Result<UniqueWrite<bool>, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_role_id(&id, replier)?;
+
+    let mut update = Document::new();
+
+    if let Some(name) = name {
+        update.insert("name", name);
+    }
+
+    if let Some(permissions) = permissions {
+        update.insert("permissions", permissions);
+    }
+
+    if let Some(parents) = parents {
+        update.insert("parents", parents);
+    }
+
+    let role_write = match client
+        .database(DATABASE)
+        .collection::<Document>(ROLE_COLLECTION)
+        .update_one(
+            doc! {
+                "_id": object_id,
+                "organization_id": organization_id
+            },
+            doc! {
+                "$set": update
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => UniqueWrite::Written(result.matched_count > 0),
+        Err(error) if is_duplicate_key_error(&error) => UniqueWrite::Duplicate,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.update_role] failed to update role: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage, update_role, failed to reply to logic with an error");
+            }
+
+            return Err(error);
+        }
+    };
+
+    if let Err(_) = replier.send(Ok(role_write)) {
+        log::warn!("storage, update_role, failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+/// Deletes the role unless other roles inherit from it or members or invitation codes of the
+/// organization reference it. Checking the references before deleting would let a concurrent
+/// write reference the role in between, so they are checked after the delete instead and the
+/// role is restored when any is found.
+pub async fn delete_role(
+    client: Client,
+    id: String,
+    organization_id: String,
+    replier: Sender<Result<RoleDeletion, Error>>,
+) -> Result<(), Error> {
+    let (object_id, replier) = parse_role_id(&id, replier)?;
+
+    match delete_unreferenced_role(&client, object_id, &id, &organization_id).await {
+        Ok(role_deletion) => {
+            if let Err(_) = replier.send(Ok(role_deletion)) {
+                log::warn!("storage, delete_role, failed to reply to logic with an ok");
+            }
+
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage, delete_role, failed to reply to logic with an error");
+            }
+
+            Err(error)
+        }
+    }
+}
+
+async fn delete_unreferenced_role(
+    client: &Client,
+    object_id: ObjectId,
+    id: &str,
+    organization_id: &str,
+) -> Result<RoleDeletion, Error> {
+    let database = client.database(DATABASE);
+    let roles = database.collection::<Document>(ROLE_COLLECTION);
+
+    let previous = match roles
+        .find_one_and_delete(
+            doc! {
+                "_id": object_id,
+                "organization_id": organization_id
+            },
+            None,
+        )
+        .await
+    {
+        Ok(Some(previous)) => previous,
+        Ok(None) => return Ok(RoleDeletion::NotFound),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.delete_role] failed to delete role: {}",
+                    &error
+                ),
+            ))
+        }
+    };
+
+    let inheriting = roles
+        .count_documents(
+            doc! {
+                "organization_id": organization_id,
+                "parents": id
+            },
+            None,
+        )
+        .await;
+
+    let role_deletion = match inheriting {
+        Ok(inheriting) if inheriting > 0u64 => Ok(RoleDeletion::Inherited),
+        Ok(_) => match database
+            .collection::<Document>(MEMBER_COLLECTION)
+            .count_documents(
+                doc! {
+                    "organization_id": organization_id,
+                    "roles": id
+                },
+                None,
+            )
+            .await
+        {
+            Ok(holders) if holders > 0u64 => Ok(RoleDeletion::Held(holders)),
+            Ok(_) => match database
+                .collection::<Document>(INVITATION_CODE_COLLECTION)
+                .count_documents(
+                    doc! {
+                        "org_id": organization_id,
+                        "roles": id
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(granting) if granting > 0u64 => Ok(RoleDeletion::Granted(granting)),
+                Ok(_) => Ok(RoleDeletion::Deleted),
+                Err(error) => Err(error),
+            },
+            Err(error) => Err(error),
+        },
+        Err(error) => Err(error),
+    };
+
+    match role_deletion {
+        Ok(RoleDeletion::Deleted) => Ok(RoleDeletion::Deleted),
+        // The references could not be checked, so the role is restored as if one was found.
+        role_deletion => {
+            if let Err(error) = roles.insert_one(&previous, None).await {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.delete_role] failed to restore referenced role: {}",
+                        &error
+                    ),
+                ));
+            }
+
+            match role_deletion {
+                Ok(role_deletion) => Ok(role_deletion),
+                Err(error) => Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.delete_role] failed to count role references: {}",
+                        &error
+                    ),
+                )),
+            }
+        }
+    }
+}
+
+/// Lists the roles available to the organization, which are its own plus the global ones.
+pub async fn list_organization_roles(
+    client: Client,
+    organization_id: String,
+    replier: Sender<Result<Vec<Role>, Error>>,
+) -> Result<(), Error> {
+    let mut cursor = match client
+        .database(DATABASE)
+        .collection::<Role>(ROLE_COLLECTION)
+        .find(
+            doc! {
+                "$or": [
+                    { "organization_id": organization_id },
+                    { "organization_id": null }
+                ]
+            },
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.list_organization_roles] failed to find roles: {}",
+                    &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!(
+                    "storage, list_organization_roles, failed to reply to logic with an error"
+                );
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut roles: Vec<Role> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.list_organization_roles] failed to advance cursor: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!(
+                        "storage, list_organization_roles, failed to reply to logic with an error"
+                    );
+                }
+
+                return Err(error);
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(role) => roles.push(role),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.role_executor.list_organization_roles] failed to deserialize role: {}",
+                        &error
+                    ),
+                );
+
+                if let Err(_) = replier.send(Err(error.clone())) {
+                    log::warn!(
+                        "storage, list_organization_roles, failed to reply to logic with an error"
+                    );
+                }
+
+                return Err(error);
+            }
+        }
+    }
+
+    if let Err(_) = replier.send(Ok(roles)) {
+        log::warn!("storage, list_organization_roles, failed to reply to logic with an ok");
+    }
+
+    Ok(())
+}
+
+fn parse_role_id<T>(
+    id: &str,
+    replier: Sender<Result<T, Error>>,
+) -> Result<(ObjectId, Sender<Result<T, Error>>), Error> {
+    match ObjectId::parse_str(id) {
+        Ok(object_id) => Ok((object_id, replier)),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.role_executor.parse_role_id] invalid role id '{}': {}",
+                    id, &error
+                ),
+            );
+
+            if let Err(_) = replier.send(Err(error.clone())) {
+                log::warn!("storage, parse_role_id, failed to reply to logic with an error");
+            }
+
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use bson::{oid::ObjectId, DateTime};
+use serde::{ser::Error, Deserialize, Serialize, Serializer};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InvitationCode {
+    #[serde(
+        rename(deserialize = "_id"),
+        serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string"
+    )]
+    id: ObjectId,
+    code: String,
+    org_id: String,
+    permissions: Vec<String>,
+    #[serde(default)]
+    denied_permissions: Vec<String>,
+    roles: Vec<String>,
+    #[serde(default)]
+    uses: i64,
+    max_uses: Option<i64>,
+    #[serde(serialize_with = "serialize_optional_date_time_as_rfc3339_string")]
+    expires_at: Option<DateTime>,
+}
+
+impl InvitationCode {
+    pub fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn denied_permissions(&self) -> &[String] {
+        &self.denied_permissions
+    }
+
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    pub fn uses(&self) -> i64 {
+        self.uses
+    }
+
+    pub fn max_uses(&self) -> Option<i64> {
+        self.max_uses
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime> {
+        self.expires_at
+    }
+}
+
+fn serialize_optional_date_time_as_rfc3339_string<S: Serializer>(
+    date_time: &Option<DateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match date_time {
+        Some(date_time) => match date_time.try_to_rfc3339_string() {
+            Ok(date_time) => serializer.serialize_some(&date_time),
+            Err(error) => Err(S::Error::custom(format!(
+                "failed to format date time as RFC 3339: {}",
+                error
+            ))),
+        },
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,44 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Member {
+    #[serde(
+        rename(deserialize = "_id"),
+        serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string"
+    )]
+    id: ObjectId,
+    user_id: String,
+    organization_id: String,
+    roles: Vec<String>,
+    #[serde(default)]
+    permissions: Vec<String>,
+    #[serde(default)]
+    denied_permissions: Vec<String>,
+}
+
+impl Member {
+    pub fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    pub fn organization_id(&self) -> &str {
+        &self.organization_id
+    }
+
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn denied_permissions(&self) -> &[String] {
+        &self.denied_permissions
+    }
+}
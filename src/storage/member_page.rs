@@ -0,0 +1,168 @@
+use bson::{oid::ObjectId, Bson, Document};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::member::Member;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemberSortField {
+    JoinedAt,
+    UserId,
+}
+
+impl MemberSortField {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "joined_at" => Some(Self::JoinedAt),
+            "user_id" => Some(Self::UserId),
+            _ => None,
+        }
+    }
+
+    /// Member field the members are sorted by. Members are joined in insertion order, so their
+    /// object id doubles as the join date. Both fields are unique within an organization, which
+    /// lets the last returned value act as the cursor of the next page.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::JoinedAt => "_id",
+            Self::UserId => "user_id",
+        }
+    }
+
+    /// Filter on the sort field selecting the members after the cursor in the requested order.
+    pub fn cursor_filter(&self, cursor: &str, descending: bool) -> Result<Document, String> {
+        let cursor = match self {
+            Self::JoinedAt => match ObjectId::parse_str(cursor) {
+                Ok(cursor) => Bson::ObjectId(cursor),
+                Err(error) => return Err(format!("invalid cursor '{}': {}", cursor, &error)),
+            },
+            Self::UserId => Bson::String(cursor.to_string()),
+        };
+
+        let operator = if descending { "$lt" } else { "$gt" };
+
+        let mut condition = Document::new();
+        condition.insert(operator, cursor);
+
+        let mut filter = Document::new();
+        filter.insert(self.key(), condition);
+
+        Ok(filter)
+    }
+
+    fn cursor_of(&self, member: &Member) -> String {
+        match self {
+            Self::JoinedAt => member.id(),
+            Self::UserId => member.user_id().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MemberPage {
+    members: Vec<Member>,
+    next_cursor: Option<String>,
+}
+
+impl MemberPage {
+    pub fn new(members: Vec<Member>, next_cursor: Option<String>) -> Self {
+        Self {
+            members,
+            next_cursor,
+        }
+    }
+
+    /// Builds a page out of members fetched with a limit of one more than requested. The extra
+    /// member only signals that a next page exists, so it is dropped and the last kept member
+    /// becomes the cursor.
+    pub fn from_fetched(mut members: Vec<Member>, sort_field: MemberSortField, limit: u32) -> Self {
+        let next_cursor = if members.len() > limit as usize {
+            members.truncate(limit as usize);
+
+            members.last().map(|member| sort_field.cursor_of(member))
+        } else {
+            None
+        };
+
+        Self::new(members, next_cursor)
+    }
+
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref()
+    }
+}
+
+#[cfg(test)]
+use crate::logic::test_storage::create_test_member;
+
+#[test]
+pub fn from_fetched_returns_cursor_of_last_kept_member_when_more_were_fetched() {
+    let members = vec![
+        create_test_member("a", vec![], vec![], vec![]),
+        create_test_member("b", vec![], vec![], vec![]),
+        create_test_member("c", vec![], vec![], vec![]),
+    ];
+
+    let page = MemberPage::from_fetched(members, MemberSortField::UserId, 2);
+
+    assert_eq!(2, page.members().len());
+    assert_eq!("b", page.members()[1].user_id());
+    assert_eq!(Some("b"), page.next_cursor());
+}
+
+#[test]
+pub fn from_fetched_uses_member_id_as_joined_at_cursor() {
+    let members = vec![
+        create_test_member("a", vec![], vec![], vec![]),
+        create_test_member("b", vec![], vec![], vec![]),
+    ];
+    let expected_cursor = members[0].id();
+
+    let page = MemberPage::from_fetched(members, MemberSortField::JoinedAt, 1);
+
+    assert_eq!(1, page.members().len());
+    assert_eq!(Some(expected_cursor.as_str()), page.next_cursor());
+}
+
+#[test]
+pub fn from_fetched_returns_no_cursor_on_last_page() {
+    let members = vec![
+        create_test_member("a", vec![], vec![], vec![]),
+        create_test_member("b", vec![], vec![], vec![]),
+    ];
+
+    let page = MemberPage::from_fetched(members, MemberSortField::UserId, 2);
+
+    assert_eq!(2, page.members().len());
+    assert_eq!(None, page.next_cursor());
+}
+
+#[test]
+pub fn cursor_filter_selects_members_after_cursor_in_sort_order() {
+    let ascending = MemberSortField::UserId.cursor_filter("b", false).unwrap();
+    let descending = MemberSortField::UserId.cursor_filter("b", true).unwrap();
+
+    assert_eq!(bson::doc! { "user_id": { "$gt": "b" } }, ascending);
+    assert_eq!(bson::doc! { "user_id": { "$lt": "b" } }, descending);
+}
+
+#[test]
+pub fn cursor_filter_parses_joined_at_cursor_as_object_id() {
+    let id = ObjectId::new();
+
+    let filter = MemberSortField::JoinedAt
+        .cursor_filter(&id.to_hex(), false)
+        .unwrap();
+
+    assert_eq!(bson::doc! { "_id": { "$gt": id } }, filter);
+}
+
+#[test]
+pub fn cursor_filter_rejects_invalid_joined_at_cursor() {
+    assert!(MemberSortField::JoinedAt
+        .cursor_filter("not-an-id", false)
+        .is_err());
+}
@@ -0,0 +1,24 @@
+/// Result of a member write that must leave its organization with at least one admin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemberWrite {
+    Applied,
+    NotFound,
+    /// The write was rolled back because it removed the organization's last admin.
+    LastAdmin,
+}
+
+/// Change written to the roles of a member.
+#[derive(Debug)]
+pub enum MemberRolesChange {
+    /// Replaces the roles as a whole, provided the member still holds the `previous` ones.
+    Replace {
+        previous: Vec<String>,
+        roles: Vec<String>,
+    },
+    /// Grants `add` and revokes `remove` on top of the roles the member holds when written.
+    /// Revoking wins for roles listed in both.
+    Change {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    options::IndexOptions,
+    Database, IndexModel,
+};
+
+use crate::storage::{migrations::Migration, storage_details::INVITATION_CODE_COLLECTION};
+
+/// Lets MongoDB remove invitation codes once their expiration date is reached.
+pub struct CreateInvitationCodeExpirationIndex;
+
+#[async_trait]
+impl Migration for CreateInvitationCodeExpirationIndex {
+    fn version(&self) -> u32 {
+        1u32
+    }
+
+    fn name(&self) -> &str {
+        "create_invitation_code_expiration_index"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        let invitation_code_expiration_index = IndexModel::builder()
+            .keys(doc! { "expires_at": 1 })
+            .options(
+                IndexOptions::builder()
+                    .name("expires_at_ttl".to_string())
+                    .expire_after(Duration::from_secs(0))
+                    .build(),
+            )
+            .build();
+
+        if let Err(error) = database
+            .collection::<Document>(INVITATION_CODE_COLLECTION)
+            .create_index(invitation_code_expiration_index, None)
+            .await
+        {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.create_invitation_code_expiration_index] failed to create index: {}",
+                    &error
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    Database,
+};
+
+use crate::storage::{migrations::Migration, storage_details::MEMBER_COLLECTION};
+
+/// Members created along with their organization were stored without the `permissions` field,
+/// unlike the ones joining through an invitation code.
+pub struct BackfillMemberPermissions;
+
+#[async_trait]
+impl Migration for BackfillMemberPermissions {
+    fn version(&self) -> u32 {
+        2u32
+    }
+
+    fn name(&self) -> &str {
+        "backfill_member_permissions"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        if let Err(error) = database
+            .collection::<Document>(MEMBER_COLLECTION)
+            .update_many(
+                doc! {
+                    "permissions": {
+                        "$exists": false
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "permissions": []
+                    }
+                },
+                None,
+            )
+            .await
+        {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.backfill_member_permissions] failed to backfill member permissions: {}",
+                    &error
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
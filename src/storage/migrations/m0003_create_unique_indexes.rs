@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::IndexOptions,
+    Collection, Database, IndexModel,
+};
+
+use crate::storage::{
+    migrations::Migration,
+    storage_details::{INVITATION_CODE_COLLECTION, MEMBER_COLLECTION},
+};
+
+/// Prevents a user from joining the same organization twice and invitation codes from
+/// colliding. Duplicates stored before the indexes existed are removed first, keeping the
+/// oldest entry, since the indexes could not be built otherwise. Roles of duplicated members
+/// are merged into the kept entry so that removing a duplicate takes no role away.
+pub struct CreateUniqueIndexes;
+
+#[async_trait]
+impl Migration for CreateUniqueIndexes {
+    fn version(&self) -> u32 {
+        3u32
+    }
+
+    fn name(&self) -> &str {
+        "create_unique_indexes"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        let members = database.collection::<Document>(MEMBER_COLLECTION);
+        let member_keys = doc! { "user_id": 1, "organization_id": 1 };
+
+        remove_duplicates(&members, &member_keys, &["roles"]).await?;
+        create_unique_index(&members, member_keys, "user_id_organization_id_unique").await?;
+
+        let invitation_codes = database.collection::<Document>(INVITATION_CODE_COLLECTION);
+        let invitation_code_keys = doc! { "code": 1 };
+
+        remove_duplicates(&invitation_codes, &invitation_code_keys, &[]).await?;
+        create_unique_index(&invitation_codes, invitation_code_keys, "code_unique").await?;
+
+        Ok(())
+    }
+}
+
+/// Removes every entry sharing `keys` with an older one, after adding the values of the
+/// `merged` array fields of the removed entries to the kept one.
+async fn remove_duplicates(
+    collection: &Collection<Document>,
+    keys: &Document,
+    merged: &[&str],
+) -> Result<(), Error> {
+    let mut group_id = Document::new();
+
+    for key in keys.keys() {
+        group_id.insert(key, format!("${}", key));
+    }
+
+    let mut group = doc! {
+        "_id": group_id,
+        "ids": {
+            "$push": "$_id"
+        },
+        "count": {
+            "$sum": 1
+        }
+    };
+
+    for field in merged {
+        group.insert(*field, doc! { "$push": format!("${}", field) });
+    }
+
+    let pipeline = vec![
+        doc! {
+            "$sort": {
+                "_id": 1
+            }
+        },
+        doc! {
+            "$group": group
+        },
+        doc! {
+            "$match": {
+                "count": {
+                    "$gt": 1
+                }
+            }
+        },
+    ];
+
+    let mut cursor = match collection.aggregate(pipeline, None).await {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                "[storage.migrations.create_unique_indexes] failed to find duplicates in '{}': {}",
+                collection.name(),
+                &error
+            ),
+            ))
+        }
+    };
+
+    let mut duplicate_ids: Vec<Bson> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.create_unique_indexes] failed to advance cursor: {}",
+                        &error
+                    ),
+                ))
+            }
+        }
+
+        let group = match cursor.deserialize_current() {
+            Ok(group) => group,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.create_unique_indexes] failed to deserialize duplicates: {}",
+                        &error
+                    ),
+                ))
+            }
+        };
+
+        let ids = match group.get_array("ids") {
+            Ok(ids) => ids,
+            Err(_) => continue,
+        };
+
+        let kept_id = match ids.first() {
+            Some(kept_id) => kept_id.clone(),
+            None => continue,
+        };
+
+        merge_into_kept(collection, &kept_id, &group, merged).await?;
+
+        duplicate_ids.extend(ids.iter().skip(1).cloned());
+    }
+
+    if duplicate_ids.is_empty() {
+        return Ok(());
+    }
+
+    log::warn!(
+        "removing {} duplicated entries from '{}'",
+        duplicate_ids.len(),
+        collection.name()
+    );
+
+    if let Err(error) = collection
+        .delete_many(
+            doc! {
+                "_id": {
+                    "$in": duplicate_ids
+                }
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.migrations.create_unique_indexes] failed to remove duplicates from '{}': {}",
+                collection.name(),
+                &error
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn merge_into_kept(
+    collection: &Collection<Document>,
+    kept_id: &Bson,
+    group: &Document,
+    merged: &[&str],
+) -> Result<(), Error> {
+    let mut add_to_set = Document::new();
+
+    for field in merged {
+        let values: Vec<Bson> = match group.get_array(field) {
+            Ok(arrays) => arrays
+                .iter()
+                .filter_map(|array| array.as_array())
+                .flatten()
+                .cloned()
+                .collect(),
+            Err(_) => continue,
+        };
+
+        if !values.is_empty() {
+            add_to_set.insert(*field, doc! { "$each": values });
+        }
+    }
+
+    if add_to_set.is_empty() {
+        return Ok(());
+    }
+
+    if let Err(error) = collection
+        .update_one(
+            doc! {
+                "_id": kept_id
+            },
+            doc! {
+                "$addToSet": add_to_set
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.migrations.create_unique_indexes] failed to merge duplicates in '{}': {}",
+                collection.name(),
+                &error
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn create_unique_index(
+    collection: &Collection<Document>,
+    keys: Document,
+    name: &str,
+) -> Result<(), Error> {
+    let index = IndexModel::builder()
+        .keys(keys)
+        .options(
+            IndexOptions::builder()
+                .name(name.to_string())
+                .unique(true)
+                .build(),
+        )
+        .build();
+
+    if let Err(error) = collection.create_index(index, None).await {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.migrations.create_unique_indexes] failed to create index '{}': {}",
+                name, &error
+            ),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    options::FindOneOptions,
+    Database,
+};
+
+use crate::storage::{
+    migrations::Migration,
+    storage_details::{MEMBER_COLLECTION, ORGANIZATION_COLLECTION, ROLE_COLLECTION},
+};
+
+/// Organizations created before ownership existed are handed to their oldest admin, which is
+/// the user who created them unless the admin role was reassigned since.
+pub struct BackfillOrganizationOwner;
+
+#[async_trait]
+impl Migration for BackfillOrganizationOwner {
+    fn version(&self) -> u32 {
+        4u32
+    }
+
+    fn name(&self) -> &str {
+        "backfill_organization_owner"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        let admin_role = match database
+            .collection::<Document>(ROLE_COLLECTION)
+            .find_one(doc! { "default_admin": true }, None)
+            .await
+        {
+            Ok(admin_role) => admin_role,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.backfill_organization_owner] failed to find the admin role: {}",
+                        &error
+                    ),
+                ))
+            }
+        };
+
+        // Without the admin role no organization could have been created yet.
+        let admin_role_id = match admin_role.and_then(|role| role.get_object_id("_id").ok()) {
+            Some(admin_role_id) => admin_role_id.to_hex(),
+            None => return Ok(()),
+        };
+
+        let organizations = database.collection::<Document>(ORGANIZATION_COLLECTION);
+        let members = database.collection::<Document>(MEMBER_COLLECTION);
+
+        let mut cursor = match organizations
+            .find(doc! { "owner": { "$exists": false } }, None)
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.backfill_organization_owner] failed to find organizations without owner: {}",
+                        &error
+                    ),
+                ))
+            }
+        };
+
+        let oldest_first = FindOneOptions::builder().sort(doc! { "_id": 1 }).build();
+
+        loop {
+            match cursor.advance().await {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.backfill_organization_owner] failed to advance cursor: {}",
+                            &error
+                        ),
+                    ))
+                }
+            }
+
+            let organization_id = match cursor.current().get_object_id("_id") {
+                Ok(organization_id) => organization_id,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.backfill_organization_owner] failed to read organization id: {}",
+                            &error
+                        ),
+                    ))
+                }
+            };
+
+            let admin = match members
+                .find_one(
+                    doc! {
+                        "organization_id": organization_id.to_hex(),
+                        "roles": &admin_role_id
+                    },
+                    oldest_first.clone(),
+                )
+                .await
+            {
+                Ok(admin) => admin,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.backfill_organization_owner] failed to find organization admin: {}",
+                            &error
+                        ),
+                    ))
+                }
+            };
+
+            let owner = match admin
+                .as_ref()
+                .and_then(|admin| admin.get_str("user_id").ok())
+            {
+                Some(owner) => owner.to_string(),
+                None => {
+                    log::warn!(
+                        "organization '{}' has no admin to become its owner",
+                        organization_id.to_hex()
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(error) = organizations
+                .update_one(
+                    doc! { "_id": organization_id },
+                    doc! { "$set": { "owner": owner } },
+                    None,
+                )
+                .await
+            {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.backfill_organization_owner] failed to set organization owner: {}",
+                        &error
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    Database,
+};
+
+use crate::storage::{migrations::Migration, storage_details::MEMBER_COLLECTION};
+
+/// Members stored before permissions could be denied lack the `denied_permissions` field.
+pub struct BackfillMemberDeniedPermissions;
+
+#[async_trait]
+impl Migration for BackfillMemberDeniedPermissions {
+    fn version(&self) -> u32 {
+        5u32
+    }
+
+    fn name(&self) -> &str {
+        "backfill_member_denied_permissions"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        if let Err(error) = database
+            .collection::<Document>(MEMBER_COLLECTION)
+            .update_many(
+                doc! {
+                    "denied_permissions": {
+                        "$exists": false
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "denied_permissions": []
+                    }
+                },
+                None,
+            )
+            .await
+        {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.backfill_member_denied_permissions] failed to backfill member denied permissions: {}",
+                    &error
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
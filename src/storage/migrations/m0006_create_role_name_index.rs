@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Document},
+    options::IndexOptions,
+    Database, IndexModel,
+};
+
+use crate::storage::{migrations::Migration, storage_details::ROLE_COLLECTION};
+
+const INDEX_NAME: &str = "organization_id_name_unique";
+
+/// Prevents an organization from holding two custom roles with the same name. Global roles do
+/// not belong to any organization and are left out of the index. Duplicates stored before the
+/// index existed are renamed after their id, keeping the oldest role's name, since members may
+/// still hold them.
+pub struct CreateRoleNameIndex;
+
+#[async_trait]
+impl Migration for CreateRoleNameIndex {
+    fn version(&self) -> u32 {
+        6u32
+    }
+
+    fn name(&self) -> &str {
+        "create_role_name_index"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        let roles = database.collection::<Document>(ROLE_COLLECTION);
+
+        let pipeline = vec![
+            doc! {
+                "$match": {
+                    "organization_id": {
+                        "$type": "string"
+                    }
+                }
+            },
+            doc! {
+                "$sort": {
+                    "_id": 1
+                }
+            },
+            doc! {
+                "$group": {
+                    "_id": {
+                        "organization_id": "$organization_id",
+                        "name": "$name"
+                    },
+                    "ids": {
+                        "$push": "$_id"
+                    }
+                }
+            },
+            doc! {
+                "$match": {
+                    "ids.1": {
+                        "$exists": true
+                    }
+                }
+            },
+        ];
+
+        let mut cursor = match roles.aggregate(pipeline, None).await {
+            Ok(cursor) => cursor,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.create_role_name_index] failed to find duplicated role names: {}",
+                        &error
+                    ),
+                ))
+            }
+        };
+
+        let mut duplicates: Vec<Document> = Vec::new();
+
+        loop {
+            match cursor.advance().await {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                        "[storage.migrations.create_role_name_index] failed to advance cursor: {}",
+                        &error
+                    ),
+                    ))
+                }
+            }
+
+            match cursor.deserialize_current() {
+                Ok(duplicate) => duplicates.push(duplicate),
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.create_role_name_index] failed to deserialize duplicated role names: {}",
+                            &error
+                        ),
+                    ))
+                }
+            }
+        }
+
+        for duplicate in duplicates {
+            let name = duplicate
+                .get_document("_id")
+                .ok()
+                .and_then(|group| group.get_str("name").ok())
+                .unwrap_or_default()
+                .to_string();
+
+            let ids = match duplicate.get_array("ids") {
+                Ok(ids) => ids.clone(),
+                Err(_) => continue,
+            };
+
+            for id in ids.into_iter().skip(1) {
+                let renamed = match id.as_object_id() {
+                    Some(object_id) => format!("{} ({})", name, object_id),
+                    None => continue,
+                };
+
+                log::warn!("renaming duplicated role '{}' to '{}'", name, renamed);
+
+                if let Err(error) = roles
+                    .update_one(
+                        doc! {
+                            "_id": id
+                        },
+                        doc! {
+                            "$set": {
+                                "name": renamed
+                            }
+                        },
+                        None,
+                    )
+                    .await
+                {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.create_role_name_index] failed to rename duplicated role: {}",
+                            &error
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let index = IndexModel::builder()
+            .keys(doc! { "organization_id": 1, "name": 1 })
+            .options(
+                IndexOptions::builder()
+                    .name(INDEX_NAME.to_string())
+                    .unique(true)
+                    .partial_filter_expression(doc! {
+                        "organization_id": {
+                            "$type": "string"
+                        }
+                    })
+                    .build(),
+            )
+            .build();
+
+        if let Err(error) = roles.create_index(index, None).await {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.create_role_name_index] failed to create index '{}': {}",
+                    INDEX_NAME, &error
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
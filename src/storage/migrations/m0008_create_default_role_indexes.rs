@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::{FindOptions, IndexOptions},
+    Database, IndexModel,
+};
+
+use crate::storage::{
+    migrations::Migration,
+    storage_details::{INVITATION_CODE_COLLECTION, MEMBER_COLLECTION, ROLE_COLLECTION},
+};
+
+const DEFAULT_ROLE_FLAGS: [&str; 2] = ["default_admin", "default_member"];
+
+/// Ensures a single default admin and a single default member role exist, as every instance
+/// seeds them on startup. Duplicates seeded concurrently before the indexes existed are merged
+/// into the oldest role, moving the members, invitation codes and child roles holding them.
+pub struct CreateDefaultRoleIndexes;
+
+#[async_trait]
+impl Migration for CreateDefaultRoleIndexes {
+    fn version(&self) -> u32 {
+        8u32
+    }
+
+    fn name(&self) -> &str {
+        "create_default_role_indexes"
+    }
+
+    async fn up(&self, database: &Database) -> Result<(), Error> {
+        for flag in DEFAULT_ROLE_FLAGS {
+            merge_duplicated_default_roles(database, flag).await?;
+            create_default_role_index(database, flag).await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn merge_duplicated_default_roles(database: &Database, flag: &str) -> Result<(), Error> {
+    let roles = database.collection::<Document>(ROLE_COLLECTION);
+
+    let mut filter = Document::new();
+    filter.insert(flag, true);
+
+    let mut cursor = match roles
+        .find(
+            filter,
+            FindOptions::builder().sort(doc! { "_id": 1 }).build(),
+        )
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                "[storage.migrations.create_default_role_indexes] failed to find '{}' roles: {}",
+                flag, &error
+            ),
+            ))
+        }
+    };
+
+    let mut ids: Vec<Bson> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                    "[storage.migrations.create_default_role_indexes] failed to advance cursor: {}",
+                    &error
+                ),
+                ))
+            }
+        }
+
+        match cursor.deserialize_current() {
+            Ok(role) => match role.get("_id") {
+                Some(id) => ids.push(id.clone()),
+                None => continue,
+            },
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.create_default_role_indexes] failed to deserialize '{}' role: {}",
+                        flag, &error
+                    ),
+                ))
+            }
+        }
+    }
+
+    let kept_id = match ids.first().and_then(|id| id.as_object_id()) {
+        Some(kept_id) => kept_id.to_hex(),
+        None => return Ok(()),
+    };
+
+    for id in ids.into_iter().skip(1) {
+        let duplicated_id = match id.as_object_id() {
+            Some(duplicated_id) => duplicated_id.to_hex(),
+            None => continue,
+        };
+
+        log::warn!(
+            "merging duplicated '{}' role '{}' into '{}'",
+            flag,
+            duplicated_id,
+            kept_id
+        );
+
+        for (collection, field) in [
+            (MEMBER_COLLECTION, "roles"),
+            (INVITATION_CODE_COLLECTION, "roles"),
+            (ROLE_COLLECTION, "parents"),
+        ] {
+            replace_role_reference(database, collection, field, &duplicated_id, &kept_id).await?;
+        }
+
+        if let Err(error) = roles.delete_one(doc! { "_id": id }, None).await {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.create_default_role_indexes] failed to delete duplicated '{}' role: {}",
+                    flag, &error
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// `$addToSet` and `$pull` cannot target the same field within one update, so the kept role is
+/// added before the duplicated one is removed.
+async fn replace_role_reference(
+    database: &Database,
+    collection: &str,
+    field: &str,
+    duplicated_id: &str,
+    kept_id: &str,
+) -> Result<(), Error> {
+    let collection = database.collection::<Document>(collection);
+
+    let mut filter = Document::new();
+    filter.insert(field, duplicated_id);
+
+    let mut added = Document::new();
+    added.insert(field, kept_id);
+
+    let mut removed = Document::new();
+    removed.insert(field, duplicated_id);
+
+    for update in [doc! { "$addToSet": added }, doc! { "$pull": removed }] {
+        if let Err(error) = collection.update_many(filter.clone(), update, None).await {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.create_default_role_indexes] failed to replace role '{}' with '{}': {}",
+                    duplicated_id, kept_id, &error
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_default_role_index(database: &Database, flag: &str) -> Result<(), Error> {
+    let mut keys = Document::new();
+    keys.insert(flag, 1);
+
+    let mut partial_filter_expression = Document::new();
+    partial_filter_expression.insert(flag, true);
+
+    let index_name = format!("{}_unique", flag);
+
+    let index = IndexModel::builder()
+        .keys(keys)
+        .options(
+            IndexOptions::builder()
+                .name(index_name.clone())
+                .unique(true)
+                .partial_filter_expression(partial_filter_expression)
+                .build(),
+        )
+        .build();
+
+    if let Err(error) = database
+        .collection::<Document>(ROLE_COLLECTION)
+        .create_index(index, None)
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.migrations.create_default_role_indexes] failed to create index '{}': {}",
+                &index_name, &error
+            ),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use std::time::Duration;
+
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, DateTime, Document},
+    options::{FindOptions, UpdateOptions},
+    Client, Collection,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::storage::{
+    migrations::Migration,
+    storage_details::{DATABASE, MIGRATION_COLLECTION, MIGRATION_LOCK_COLLECTION},
+    storage_error::is_duplicate_key_error,
+};
+
+const MIGRATION_LOCK_ID: &str = "migrations";
+/// A migrator not renewing its lock for longer is assumed to have died, letting others take over.
+const MIGRATION_LOCK_LEASE_IN_MILLISECONDS: i64 = 600000i64;
+const MIGRATION_LOCK_RETRY_INTERVAL_IN_MILLISECONDS: u64 = 1000u64;
+/// The lease is renewed well before expiring so that a slow migration keeps the lock.
+const MIGRATION_LOCK_RENEW_INTERVAL_IN_MILLISECONDS: u64 = 60000u64;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AppliedMigration {
+    #[serde(rename = "_id")]
+    version: i64,
+    name: String,
+    applied_at: DateTime,
+}
+
+impl AppliedMigration {
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn applied_at(&self) -> DateTime {
+        self.applied_at
+    }
+}
+
+/// Applies the migrations which have not been recorded yet in the migration collection. Every
+/// instance of the service migrates on startup, so runs are serialized through a lock document
+/// which records the migrator owning it.
+pub struct Migrator {
+    client: Client,
+    migrations: Vec<Box<dyn Migration>>,
+    owner: String,
+}
+
+impl Migrator {
+    pub fn new(client: Client, mut migrations: Vec<Box<dyn Migration>>) -> Self {
+        migrations.sort_by_key(|migration| migration.version());
+
+        Self {
+            client,
+            migrations,
+            owner: Uuid::new_v4().to_string(),
+        }
+    }
+
+    pub fn migrations(&self) -> &[Box<dyn Migration>] {
+        &self.migrations
+    }
+
+    pub async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, Error> {
+        let mut cursor = match self
+            .collection()
+            .find(
+                None,
+                FindOptions::builder().sort(doc! { "_id": 1 }).build(),
+            )
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.migrator.applied_migrations] failed to find applied migrations: {}",
+                        &error
+                    ),
+                ))
+            }
+        };
+
+        let mut applied_migrations: Vec<AppliedMigration> = Vec::new();
+
+        loop {
+            match cursor.advance().await {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.migrator.applied_migrations] failed to advance cursor: {}",
+                            &error
+                        ),
+                    ))
+                }
+            }
+
+            match cursor.deserialize_current() {
+                Ok(applied_migration) => applied_migrations.push(applied_migration),
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageError,
+                        format!(
+                            "[storage.migrations.migrator.applied_migrations] failed to deserialize applied migration: {}",
+                            &error
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(applied_migrations)
+    }
+
+    pub async fn pending_migrations(&self) -> Result<Vec<&dyn Migration>, Error> {
+        let applied_migrations = self.applied_migrations().await?;
+
+        Ok(pending_migrations(&self.migrations, &applied_migrations))
+    }
+
+    /// Runs every pending migration in order, stopping at the first failure so later
+    /// migrations never run on top of an incomplete one. Waits for any other migrator to finish
+    /// first, so migrations it applied meanwhile are not applied again. The lock is renewed in
+    /// the background while migrating. Returns the applied versions.
+    pub async fn run_pending(&self) -> Result<Vec<u32>, Error> {
+        self.acquire_lock().await?;
+
+        let renewal = tokio::spawn(renew_lock_periodically(
+            self.lock_collection(),
+            self.owner.clone(),
+        ));
+
+        let result = self.apply_pending().await;
+
+        renewal.abort();
+
+        if let Err(error) = self.release_lock().await {
+            log::warn!("{}", &error);
+        }
+
+        result
+    }
+
+    async fn apply_pending(&self) -> Result<Vec<u32>, Error> {
+        let database = self.client.database(DATABASE);
+
+        let mut applied_versions: Vec<u32> = Vec::new();
+
+        for migration in self.pending_migrations().await? {
+            if !renew_lock(&self.lock_collection(), &self.owner).await? {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    "[storage.migrations.migrator.run_pending] migration lock was lost to another migrator",
+                ));
+            }
+
+            log::info!(
+                "applying migration {} '{}'",
+                migration.version(),
+                migration.name()
+            );
+
+            migration.up(&database).await?;
+
+            let applied_migration = AppliedMigration {
+                version: migration.version() as i64,
+                name: migration.name().to_string(),
+                applied_at: DateTime::now(),
+            };
+
+            if let Err(error) = self.collection().insert_one(applied_migration, None).await {
+                if is_duplicate_key_error(&error) {
+                    log::warn!(
+                        "migration {} was recorded meanwhile by another migrator",
+                        migration.version()
+                    );
+
+                    continue;
+                }
+
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!(
+                        "[storage.migrations.migrator.run_pending] failed to record migration {}: {}",
+                        migration.version(),
+                        &error
+                    ),
+                ));
+            }
+
+            applied_versions.push(migration.version());
+        }
+
+        Ok(applied_versions)
+    }
+
+    async fn acquire_lock(&self) -> Result<(), Error> {
+        let waiting_since = DateTime::now();
+
+        while !self.try_acquire_lock().await? {
+            if DateTime::now().timestamp_millis() - waiting_since.timestamp_millis()
+                > MIGRATION_LOCK_LEASE_IN_MILLISECONDS
+            {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    "[storage.migrations.migrator.acquire_lock] timed out waiting for another migrator to finish",
+                ));
+            }
+
+            log::info!("waiting for another migrator to finish");
+
+            tokio::time::sleep(Duration::from_millis(
+                MIGRATION_LOCK_RETRY_INTERVAL_IN_MILLISECONDS,
+            ))
+            .await;
+        }
+
+        Ok(())
+    }
+
+    /// Takes the lock unless another migrator holds an unexpired one. The upsert of a held lock
+    /// fails with a duplicate key, as its filter does not match the existing lock document.
+    async fn try_acquire_lock(&self) -> Result<bool, Error> {
+        let now = DateTime::now();
+
+        match self
+            .lock_collection()
+            .update_one(
+                lock_filter(now),
+                doc! {
+                    "$set": {
+                        "owner": &self.owner,
+                        "locked_until": DateTime::from_millis(
+                            now.timestamp_millis() + MIGRATION_LOCK_LEASE_IN_MILLISECONDS
+                        )
+                    }
+                },
+                UpdateOptions::builder().upsert(true).build(),
+            )
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(error) if is_duplicate_key_error(&error) => Ok(false),
+            Err(error) => Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.migrator.try_acquire_lock] failed to acquire migration lock: {}",
+                    &error
+                ),
+            )),
+        }
+    }
+
+    /// Deletes the lock only while this migrator owns it, so a lock taken over after the lease
+    /// expired is left to its new owner.
+    async fn release_lock(&self) -> Result<(), Error> {
+        if let Err(error) = self
+            .lock_collection()
+            .delete_one(owned_lock_filter(&self.owner), None)
+            .await
+        {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.migrations.migrator.release_lock] failed to release migration lock: {}",
+                    &error
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn lock_collection(&self) -> Collection<Document> {
+        self.client
+            .database(DATABASE)
+            .collection::<Document>(MIGRATION_LOCK_COLLECTION)
+    }
+
+    fn collection(&self) -> Collection<AppliedMigration> {
+        self.client
+            .database(DATABASE)
+            .collection::<AppliedMigration>(MIGRATION_COLLECTION)
+    }
+}
+
+fn pending_migrations<'a>(
+    migrations: &'a [Box<dyn Migration>],
+    applied_migrations: &[AppliedMigration],
+) -> Vec<&'a dyn Migration> {
+    migrations
+        .iter()
+        .filter(|migration| {
+            !applied_migrations
+                .iter()
+                .any(|applied| applied.version() == migration.version() as i64)
+        })
+        .map(|migration| migration.as_ref())
+        .collect()
+}
+
+/// Extends the lease of the lock owned by `owner`, returning whether it still was owned.
+async fn renew_lock(collection: &Collection<Document>, owner: &str) -> Result<bool, Error> {
+    let locked_until = DateTime::from_millis(
+        DateTime::now().timestamp_millis() + MIGRATION_LOCK_LEASE_IN_MILLISECONDS,
+    );
+
+    match collection
+        .update_one(
+            owned_lock_filter(owner),
+            doc! {
+                "$set": {
+                    "locked_until": locked_until
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) => Ok(result.matched_count > 0),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.migrations.migrator.renew_lock] failed to renew migration lock: {}",
+                &error
+            ),
+        )),
+    }
+}
+
+async fn renew_lock_periodically(collection: Collection<Document>, owner: String) {
+    loop {
+        tokio::time::sleep(Duration::from_millis(
+            MIGRATION_LOCK_RENEW_INTERVAL_IN_MILLISECONDS,
+        ))
+        .await;
+
+        match renew_lock(&collection, &owner).await {
+            Ok(true) => (),
+            Ok(false) => {
+                log::error!("migration lock was lost to another migrator");
+
+                return;
+            }
+            Err(error) => log::warn!("{}", &error),
+        }
+    }
+}
+
+/// Matches the lock document only while `owner` holds it.
+fn owned_lock_filter(owner: &str) -> Document {
+    doc! {
+        "_id": MIGRATION_LOCK_ID,
+        "owner": owner
+    }
+}
+
+/// Matches the lock document only once its lease has expired.
+fn lock_filter(now: DateTime) -> Document {
+    doc! {
+        "_id": MIGRATION_LOCK_ID,
+        "locked_until": {
+            "$lt": now
+        }
+    }
+}
+
+#[cfg(test)]
+struct TestMigration(u32);
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl Migration for TestMigration {
+    fn version(&self) -> u32 {
+        self.0
+    }
+
+    fn name(&self) -> &str {
+        "test_migration"
+    }
+
+    async fn up(&self, _: &mongodb::Database) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn create_test_applied_migration(version: i64) -> AppliedMigration {
+    AppliedMigration {
+        version,
+        name: "test_migration".to_string(),
+        applied_at: DateTime::now(),
+    }
+}
+
+#[test]
+pub fn pending_migrations_skip_recorded_versions_keeping_order() {
+    let migrations: Vec<Box<dyn Migration>> = vec![
+        Box::new(TestMigration(1)),
+        Box::new(TestMigration(2)),
+        Box::new(TestMigration(3)),
+        Box::new(TestMigration(4)),
+    ];
+    let applied_migrations = vec![
+        create_test_applied_migration(1),
+        create_test_applied_migration(3),
+    ];
+
+    let pending: Vec<u32> = pending_migrations(&migrations, &applied_migrations)
+        .iter()
+        .map(|migration| migration.version())
+        .collect();
+
+    assert_eq!(vec![2, 4], pending);
+}
+
+#[test]
+pub fn pending_migrations_are_empty_when_every_version_is_recorded() {
+    let migrations: Vec<Box<dyn Migration>> =
+        vec![Box::new(TestMigration(1)), Box::new(TestMigration(2))];
+    let applied_migrations = vec![
+        create_test_applied_migration(1),
+        create_test_applied_migration(2),
+    ];
+
+    assert!(pending_migrations(&migrations, &applied_migrations).is_empty());
+}
+
+#[test]
+pub fn lock_filter_only_matches_expired_lock() {
+    let now = DateTime::now();
+
+    assert_eq!(
+        doc! {
+            "_id": MIGRATION_LOCK_ID,
+            "locked_until": { "$lt": now }
+        },
+        lock_filter(now)
+    );
+}
+
+#[test]
+pub fn owned_lock_filter_only_matches_lock_of_owner() {
+    assert_eq!(
+        doc! {
+            "_id": MIGRATION_LOCK_ID,
+            "owner": "owner"
+        },
+        owned_lock_filter("owner")
+    );
+}
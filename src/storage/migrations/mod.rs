@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use cp_microservice::core::error::Error;
+use mongodb::Database;
+
+pub mod m0001_create_invitation_code_expiration_index;
+pub mod m0002_backfill_member_permissions;
+pub mod m0003_create_unique_indexes;
+pub mod m0004_backfill_organization_owner;
+pub mod m0005_backfill_member_denied_permissions;
+pub mod m0006_create_role_name_index;
+pub mod m0008_create_default_role_indexes;
+pub mod migrator;
+
+/// A versioned change to the database schema or data. Migrations are applied once, in
+/// ascending version order, and must never be edited after being released.
+#[async_trait]
+pub trait Migration: Send + Sync {
+    fn version(&self) -> u32;
+
+    fn name(&self) -> &str;
+
+    async fn up(&self, database: &Database) -> Result<(), Error>;
+}
+
+pub fn get_migrations() -> Vec<Box<dyn Migration>> {
+    vec![
+        Box::new(
+            m0001_create_invitation_code_expiration_index::CreateInvitationCodeExpirationIndex,
+        ),
+        Box::new(m0002_backfill_member_permissions::BackfillMemberPermissions),
+        Box::new(m0003_create_unique_indexes::CreateUniqueIndexes),
+        Box::new(m0004_backfill_organization_owner::BackfillOrganizationOwner),
+        Box::new(m0005_backfill_member_denied_permissions::BackfillMemberDeniedPermissions),
+        Box::new(m0006_create_role_name_index::CreateRoleNameIndex),
+        Box::new(m0008_create_default_role_indexes::CreateDefaultRoleIndexes),
+    ]
+}
@@ -1,6 +1,18 @@
 pub mod actions;
+pub mod default_roles;
 pub mod dispatch;
 pub mod executors;
+pub mod invitation_code;
+pub mod member;
+pub mod member_page;
+pub mod member_write;
+pub mod migrations;
+pub mod organization;
+pub mod organization_purge;
 pub mod role;
+pub mod role_deletion;
 pub mod storage_details;
+pub mod storage_error;
 pub mod storage_request;
+pub mod unique_write;
+pub mod user_organization;
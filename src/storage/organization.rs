@@ -0,0 +1,45 @@
+use bson::{oid::ObjectId, DateTime};
+use cp_core::geolocalization::address::Address;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Organization {
+    #[serde(
+        rename(deserialize = "_id"),
+        serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string"
+    )]
+    id: ObjectId,
+    country: String,
+    name: String,
+    address: Address,
+    #[serde(default)]
+    owner: String,
+    #[serde(default, skip_serializing)]
+    deleted_at: Option<DateTime>,
+}
+
+impl Organization {
+    pub fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn deleted_at(&self) -> Option<DateTime> {
+        self.deleted_at
+    }
+}
@@ -0,0 +1,242 @@
+use std::time::Duration;
+
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::{
+    bson::{doc, oid::ObjectId, DateTime, Document},
+    options::FindOptions,
+    Client, Database,
+};
+
+use crate::storage::storage_details::{
+    DATABASE, INVITATION_CODE_COLLECTION, MEMBER_COLLECTION, ORGANIZATION_COLLECTION,
+    ROLE_COLLECTION,
+};
+
+const PURGE_INTERVAL_IN_SECONDS: u64 = 3600u64;
+
+/// Periodically removes organizations whose soft deletion is older than the grace period,
+/// together with their members, invitation codes and custom roles.
+pub struct OrganizationPurge {
+    client: Client,
+    grace_period: Duration,
+}
+
+impl OrganizationPurge {
+    pub fn new(client: Client, grace_period: Duration) -> Self {
+        Self {
+            client,
+            grace_period,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(PURGE_INTERVAL_IN_SECONDS));
+
+        loop {
+            interval.tick().await;
+
+            match purge_deleted_organizations(&self.client, self.grace_period).await {
+                Ok(0) => (),
+                Ok(purged) => log::info!("purged {} deleted organizations", purged),
+                Err(error) => log::warn!("failed to purge deleted organizations: {}", &error),
+            }
+        }
+    }
+}
+
+async fn purge_deleted_organizations(
+    client: &Client,
+    grace_period: Duration,
+) -> Result<u64, Error> {
+    let deleted_before =
+        DateTime::from_millis(DateTime::now().timestamp_millis() - grace_period.as_millis() as i64);
+
+    let database = client.database(DATABASE);
+
+    let mut cursor = match database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .find(
+            deleted_before_filter(deleted_before),
+            FindOptions::builder().projection(doc! { "_id": 1 }).build(),
+        )
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!("[storage.organization_purge.purge_deleted_organizations] failed to find deleted organizations: {}", &error),
+            ))
+        }
+    };
+
+    let mut object_ids: Vec<ObjectId> = Vec::new();
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!("[storage.organization_purge.purge_deleted_organizations] failed to advance cursor: {}", &error),
+                ))
+            }
+        }
+
+        match cursor.current().get_object_id("_id") {
+            Ok(object_id) => object_ids.push(object_id),
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageError,
+                    format!("[storage.organization_purge.purge_deleted_organizations] failed to read organization id: {}", &error),
+                ))
+            }
+        }
+    }
+
+    let mut purged = 0u64;
+
+    for object_id in object_ids {
+        if purge_organization(&database, object_id, deleted_before).await? {
+            purged += 1u64;
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Filter matching the organizations soft deleted no later than `deleted_before`.
+fn deleted_before_filter(deleted_before: DateTime) -> Document {
+    doc! {
+        "deleted_at": {
+            "$lte": deleted_before
+        }
+    }
+}
+
+/// Marks the organization as being purged unless it was restored or deleted again since it was
+/// found, which keeps it from being restored. Its members, invitation codes and custom roles are
+/// removed next and the organization last, so a purge failing midway is retried by the next one
+/// instead of leaving them behind.
+async fn purge_organization(
+    database: &Database,
+    object_id: ObjectId,
+    deleted_before: DateTime,
+) -> Result<bool, Error> {
+    let mut filter = deleted_before_filter(deleted_before);
+    filter.insert("_id", object_id);
+
+    match database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .update_one(
+            filter,
+            doc! {
+                "$set": {
+                    "purging": true
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count > 0 => (),
+        Ok(_) => return Ok(false),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageError,
+                format!(
+                    "[storage.organization_purge.purge_organization] failed to mark organization as purging: {}",
+                    &error
+                ),
+            ))
+        }
+    }
+
+    let organization_id = object_id.to_string();
+
+    if let Err(error) = database
+        .collection::<Document>(MEMBER_COLLECTION)
+        .delete_many(
+            doc! {
+                "organization_id": &organization_id
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_purge.purge_organization] failed to delete members of organization '{}': {}", &organization_id, &error),
+        ));
+    }
+
+    if let Err(error) = database
+        .collection::<Document>(INVITATION_CODE_COLLECTION)
+        .delete_many(
+            doc! {
+                "org_id": &organization_id
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_purge.purge_organization] failed to delete invitation codes of organization '{}': {}", &organization_id, &error),
+        ));
+    }
+
+    if let Err(error) = database
+        .collection::<Document>(ROLE_COLLECTION)
+        .delete_many(
+            doc! {
+                "organization_id": &organization_id
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!("[storage.organization_purge.purge_organization] failed to delete roles of organization '{}': {}", &organization_id, &error),
+        ));
+    }
+
+    if let Err(error) = database
+        .collection::<Document>(ORGANIZATION_COLLECTION)
+        .delete_one(
+            doc! {
+                "_id": object_id,
+                "purging": true
+            },
+            None,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageError,
+            format!(
+                "[storage.organization_purge.purge_organization] failed to delete organization '{}': {}",
+                &organization_id, &error
+            ),
+        ));
+    }
+
+    Ok(true)
+}
+
+#[test]
+pub fn purge_only_matches_organizations_still_deleted_before_grace_period() {
+    let deleted_before = DateTime::from_millis(1700000000000i64);
+
+    let filter = deleted_before_filter(deleted_before);
+
+    assert_eq!(
+        Ok(&deleted_before),
+        filter
+            .get_document("deleted_at")
+            .unwrap()
+            .get_datetime("$lte")
+    );
+}
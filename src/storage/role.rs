@@ -1,17 +1,48 @@
 use bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Role {
-    _id: ObjectId,
+    #[serde(
+        rename(deserialize = "_id"),
+        serialize_with = "bson::serde_helpers::serialize_object_id_as_hex_string"
+    )]
+    id: ObjectId,
     name: String,
     permissions: Vec<String>,
     default_admin: Option<bool>,
     default_member: Option<bool>,
+    #[serde(default)]
+    organization_id: Option<String>,
+    #[serde(default)]
+    parents: Vec<String>,
 }
 
 impl Role {
     pub fn id(&self) -> String {
-        self._id.to_string()
+        self.id.to_string()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn organization_id(&self) -> Option<&str> {
+        self.organization_id.as_deref()
+    }
+
+    pub fn parents(&self) -> &[String] {
+        &self.parents
+    }
+
+    pub fn is_available_to(&self, organization_id: &str) -> bool {
+        match &self.organization_id {
+            Some(role_organization_id) => role_organization_id == organization_id,
+            None => true,
+        }
     }
 }
@@ -0,0 +1,12 @@
+/// Result of deleting a role, which is refused while anything still references it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoleDeletion {
+    Deleted,
+    NotFound,
+    /// The deletion was rolled back because other roles inherit from the role.
+    Inherited,
+    /// The deletion was rolled back because this many members hold the role.
+    Held(u64),
+    /// The deletion was rolled back because this many invitation codes grant the role.
+    Granted(u64),
+}
@@ -4,3 +4,5 @@ pub const ORGANIZATION_COLLECTION: &str = "organization";
 pub const ROLE_COLLECTION: &str = "role";
 pub const MEMBER_COLLECTION: &str = "member";
 pub const INVITATION_CODE_COLLECTION: &str = "invitation_code";
+pub const MIGRATION_COLLECTION: &str = "migration";
+pub const MIGRATION_LOCK_COLLECTION: &str = "migration_lock";
@@ -0,0 +1,33 @@
+use cp_microservice::core::error::{Error, ErrorKind};
+use mongodb::error::WriteFailure;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+pub fn is_duplicate_key_error(error: &mongodb::error::Error) -> bool {
+    match error.kind.as_ref() {
+        mongodb::error::ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == DUPLICATE_KEY_ERROR_CODE
+        }
+        mongodb::error::ErrorKind::Command(command_error) => {
+            command_error.code == DUPLICATE_KEY_ERROR_CODE
+        }
+        mongodb::error::ErrorKind::BulkWrite(bulk_write_failure) => bulk_write_failure
+            .write_errors
+            .as_ref()
+            .is_some_and(|write_errors| {
+                write_errors
+                    .iter()
+                    .any(|write_error| write_error.code == DUPLICATE_KEY_ERROR_CODE)
+            }),
+        _ => false,
+    }
+}
+
+/// Builds the error replied for a failed write. Writes which may legitimately hit a unique index
+/// reply a `UniqueWrite` instead, so duplicates reaching this are storage failures too.
+pub fn write_error(location: &str, message: &str, error: &mongodb::error::Error) -> Error {
+    Error::new(
+        ErrorKind::StorageError,
+        format!("[{}] {}: {}", location, message, error),
+    )
+}
@@ -0,0 +1,8 @@
+/// Result of a write guarded by a unique index. Duplicates are expected outcomes, like a user
+/// joining twice through a double click, so they are told apart from storage failures here
+/// rather than through the error replied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UniqueWrite<T> {
+    Written(T),
+    Duplicate,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::storage::organization::Organization;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserOrganization {
+    organization: Organization,
+    roles: Vec<String>,
+}
+
+impl UserOrganization {
+    pub fn organization(&self) -> &Organization {
+        &self.organization
+    }
+
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+}